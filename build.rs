@@ -0,0 +1,94 @@
+/// Generates code that is included by the solutions:
+///  1. the in-process solution registry of the main binary. Each `src/bin/NN.rs` is mounted as a module and its
+///     `SOLUTION` constant is collected into `SOLUTIONS`.
+///  2. one test per example with expected answers, i.e. every `data/examples/<name>.answers` file.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    write_registry(Path::new(&manifest_dir), Path::new(&out_dir));
+    write_example_tests(Path::new(&manifest_dir), Path::new(&out_dir));
+}
+
+fn write_registry(manifest_dir: &Path, out_dir: &Path) {
+    let bin_dir = manifest_dir.join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut days: Vec<u8> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let stem = name.strip_suffix(".rs")?;
+                    if stem.len() != 2 {
+                        return None;
+                    }
+                    stem.parse::<u8>().ok().filter(|day| (1..=25).contains(day))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort_unstable();
+
+    let mut out = String::new();
+
+    for day in &days {
+        let path = bin_dir.join(format!("{day:02}.rs"));
+        out.push_str(&format!(
+            "#[path = {:?}]\n#[allow(warnings, clippy::all, clippy::pedantic)]\nmod day_{day:02};\n",
+            path.display().to_string()
+        ));
+    }
+
+    out.push_str("\npub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for day in &days {
+        out.push_str(&format!("    day_{day:02}::SOLUTION,\n"));
+    }
+    out.push_str("];\n");
+
+    fs::write(out_dir.join("solutions.rs"), out).unwrap();
+}
+
+/// Write `examples_<day>.rs` for every day, containing a test for each of its examples with expected answers.
+/// Examples belong to a day if their name starts with the zero-padded day, e.g. `01.answers` or `01-2.answers`.
+fn write_example_tests(manifest_dir: &Path, out_dir: &Path) {
+    let examples_dir = manifest_dir.join("data").join("examples");
+
+    println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+    let mut names: Vec<String> = fs::read_dir(&examples_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    name.strip_suffix(".answers").map(String::from)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort_unstable();
+
+    for day in 1..=25u8 {
+        let prefix = format!("{day:02}");
+        let mut out = String::new();
+
+        for name in names.iter().filter(|name| name.starts_with(&prefix)) {
+            let test_name: String = name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+            out.push_str(&format!(
+                "#[test]\nfn example_{test_name}() {{\n    advent_of_code::template::examples::check(&super::SOLUTION, {name:?});\n}}\n\n"
+            ));
+        }
+
+        fs::write(out_dir.join(format!("examples_{day}.rs")), out).unwrap();
+    }
+}
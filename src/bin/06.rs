@@ -1,5 +1,7 @@
 use std::collections::{BTreeSet, HashSet};
 
+use advent_of_code::template::progress;
+
 advent_of_code::solution!(6);
 
 struct Grid {
@@ -117,7 +119,11 @@ pub fn part_two(input: &str) -> Option<u32> {
         _ => panic!("nope"),
     };
 
-    for position in cells {
+    let total = cells.len();
+
+    for (done, position) in cells.into_iter().enumerate() {
+        progress::report(done, total);
+
         if grid.occupied(position) {
             continue;
         }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(19);
+advent_of_code::solution!(19, parse = parse);
 
 enum TowelColor {
     White,
@@ -23,7 +23,7 @@ impl TryFrom<char> for TowelColor {
     }
 }
 
-struct Trie {
+pub struct Trie {
     white: Option<Box<Trie>>,
     blue: Option<Box<Trie>>,
     black: Option<Box<Trie>>,
@@ -108,7 +108,7 @@ fn count_possible(s: &str, trie: &Trie) -> i64 {
     score[s.len()]
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn parse(input: &str) -> (Trie, Vec<&str>) {
     let mut trie = Trie::new();
     for line in input.lines().take_while(|line| !line.is_empty()) {
         for bit in line.split(",") {
@@ -116,9 +116,19 @@ pub fn part_one(input: &str) -> Option<u64> {
         }
     }
 
+    let designs = input
+        .lines()
+        .skip_while(|line| !line.is_empty())
+        .skip(1)
+        .collect();
+
+    (trie, designs)
+}
+
+pub fn part_one((trie, designs): &(Trie, Vec<&str>)) -> Option<u64> {
     let mut count = 0;
-    for line in input.lines().skip_while(|line| !line.is_empty()).skip(1) {
-        if is_possible(line, &trie) {
+    for line in designs {
+        if is_possible(line, trie) {
             count += 1;
         }
     }
@@ -126,17 +136,10 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(count)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut trie = Trie::new();
-    for line in input.lines().take_while(|line| !line.is_empty()) {
-        for bit in line.split(",") {
-            trie.insert(bit.trim());
-        }
-    }
-
+pub fn part_two((trie, designs): &(Trie, Vec<&str>)) -> Option<u64> {
     let mut count = 0;
-    for line in input.lines().skip_while(|line| !line.is_empty()).skip(1) {
-        count += count_possible(line, &trie);
+    for line in designs {
+        count += count_possible(line, trie);
     }
     Some(count.try_into().unwrap())
 }
@@ -147,13 +150,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(16));
     }
 }
@@ -2,7 +2,7 @@ use std::{collections::HashMap, ops::AddAssign};
 
 use itertools::Itertools;
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, parse = race_track);
 
 fn dijkstra(maze: &[bool], distances: &mut [i32], size: (i32, i32), end: i32) {
     assert!(maze.len() == distances.len());
@@ -69,9 +69,36 @@ fn parse(input: &str) -> (Vec<bool>, (i32, i32), i32, i32) {
     (maze, size, start, end)
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
+/// The maze together with the distances of every position to the start and the end.
+pub struct RaceTrack {
+    maze: Vec<bool>,
+    size: (i32, i32),
+    start: i32,
+    d_start: Vec<i32>,
+    d_end: Vec<i32>,
+}
+
+pub fn race_track(input: &str) -> RaceTrack {
+    let (maze, size, start, end) = parse(input);
+
+    let mut d_end = vec![std::i32::MAX; maze.len()];
+    dijkstra(&maze, &mut d_end, size, end);
+
+    let mut d_start = vec![std::i32::MAX; maze.len()];
+    dijkstra(&maze, &mut d_start, size, start);
+
+    RaceTrack {
+        maze,
+        size,
+        start,
+        d_start,
+        d_end,
+    }
+}
+
+pub fn part_one(track: &RaceTrack) -> Option<i32> {
     Some(
-        calculate_cheats(input, 2)
+        calculate_cheats(track, 2)
             .into_iter()
             .filter(|(k, _)| *k >= 100)
             .map(|(k, v)| v)
@@ -100,14 +127,15 @@ fn cheats(start: i32, len: usize, size: (i32, i32)) -> impl Iterator<Item = i32>
         .map(move |(x, y)| y * size.1 + x)
 }
 
-pub fn calculate_cheats(input: &str, cheat_len: usize) -> HashMap<i32, i32> {
-    let (maze, size, start, end) = parse(input);
-
-    let mut d_end = vec![std::i32::MAX; maze.len()];
-    dijkstra(&maze, &mut d_end, size, end);
-
-    let mut d_start = vec![std::i32::MAX; maze.len()];
-    dijkstra(&maze, &mut d_start, size, start);
+pub fn calculate_cheats(track: &RaceTrack, cheat_len: usize) -> HashMap<i32, i32> {
+    let RaceTrack {
+        maze,
+        size,
+        start,
+        d_start,
+        d_end,
+    } = track;
+    let (size, start) = (*size, *start);
 
     // for each wall in maze
     let mut savings = HashMap::new();
@@ -142,7 +170,7 @@ pub fn calculate_cheats(input: &str, cheat_len: usize) -> HashMap<i32, i32> {
             if !valid(cheat_end as usize) || maze[cheat_end as usize] {
                 continue;
             }
-            let saving = cheat_saving(cheat_start, cheat_end, &d_start, &d_end, start);
+            let saving = cheat_saving(cheat_start, cheat_end, d_start, d_end, start);
 
             if saving < 1 {
                 continue;
@@ -158,9 +186,9 @@ pub fn calculate_cheats(input: &str, cheat_len: usize) -> HashMap<i32, i32> {
     savings
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
+pub fn part_two(track: &RaceTrack) -> Option<i32> {
     Some(
-        calculate_cheats(input, 20)
+        calculate_cheats(track, 20)
             .into_iter()
             .filter(|(k, _)| *k >= 100)
             .map(|(_k, v)| v)
@@ -208,21 +236,26 @@ mod tests {
 
     #[test]
     fn test_calculate_cheats() {
-        let cheats = calculate_cheats(&advent_of_code::template::read_file("examples", DAY), 2);
+        let cheats = calculate_cheats(
+            &race_track(&advent_of_code::template::read_file("examples", DAY)),
+            2,
+        );
         // dbg!(&cheats);
         assert_eq!(cheats.get(&2), Some(&14));
         assert_eq!(cheats.get(&36), Some(&1));
     }
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&race_track(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(0));
     }
 
     #[test]
     fn test_part_two_decomp() {
-        let input: &str = &advent_of_code::template::read_file("examples", DAY);
-        calculate_cheats(input, 20)
+        let track = race_track(&advent_of_code::template::read_file("examples", DAY));
+        calculate_cheats(&track, 20)
             .into_iter()
             .filter(|(k, _)| *k >= 50)
             .for_each(|(k, v)| println!("there are {v} cheats saving {k}"));
@@ -230,7 +263,9 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&race_track(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, None);
     }
 }
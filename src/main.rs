@@ -1,14 +1,30 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, example, inputs, read, run_day, scaffold, solve, stars, time, verify, watch,
+};
 use args::{parse, AppArguments};
+use solutions::SOLUTIONS;
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
+
+/// Solutions of all scaffolded days, compiled into this binary so they can be run in-process.
+/// The registry is generated by `build.rs` and left empty for test builds, dhat profiling and allocation counting,
+/// where each day's binary brings its own tests and global allocator.
+mod solutions {
+    #[cfg(not(any(test, feature = "dhat-heap", feature = "count-allocs")))]
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+    #[cfg(any(test, feature = "dhat-heap", feature = "count-allocs"))]
+    pub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[];
+}
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::inputs;
+    use advent_of_code::template::export::ExportFormat;
+    use advent_of_code::template::runner::{OutputFormat, Schedule, DEFAULT_TIMEOUT_SECS};
+    use advent_of_code::template::{compare, Day};
     use std::process;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
@@ -16,66 +32,149 @@ mod args {
         },
         Read {
             day: Day,
+            part: Option<u8>,
+            refresh: bool,
+        },
+        Example {
+            day: Day,
+            index: Option<usize>,
+            list: bool,
+            overwrite: bool,
         },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
+            allocs: bool,
+            input: Option<String>,
             submit: Option<u8>,
+            format: Option<OutputFormat>,
+            watch: bool,
+            test: bool,
         },
         All {
-            release: bool,
+            format: OutputFormat,
+            schedule: Schedule,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            export: Vec<ExportFormat>,
+            memory: bool,
+            format: OutputFormat,
+            compare: Option<f64>,
+            history: Option<Day>,
+            schedule: Schedule,
+        },
+        /// Internal: runs a single day in a child process of `all` and `time`.
+        RunDay {
+            day: Day,
+            is_timed: bool,
+        },
+        Verify {
+            day: Option<Day>,
+            accept: bool,
+        },
+        Stars,
+        Inputs {
+            action: inputs::Action,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+            Some("all") => {
+                // NOTE: `--release` is accepted so `cargo all --release` keeps working, but it has no effect: the
+                // solutions run in-process and the `all` alias already builds the main binary with `--release`.
+                let _release = args.contains("--release");
+                AppArguments::All {
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    schedule: parse_schedule(&mut args)?,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let export = args.values_from_str("--export")?;
+                let memory = args.contains("--memory");
+                let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+                let compare = args.contains("--compare");
+                let threshold = args.opt_value_from_str("--threshold")?;
+                let history = args.opt_value_from_str("--history")?;
+                let schedule = parse_schedule(&mut args)?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    export,
+                    memory,
+                    format,
+                    compare: compare.then(|| threshold.unwrap_or(compare::DEFAULT_THRESHOLD)),
+                    history,
+                    schedule,
                 }
             }
+            Some("run-day") => AppArguments::RunDay {
+                is_timed: args.contains("--time"),
+                day: args.free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                part: args.opt_value_from_str("--part")?,
+                refresh: args.contains("--refresh"),
                 day: args.free_from_str()?,
             },
+            Some("example") => AppArguments::Example {
+                list: args.contains("--list"),
+                overwrite: args.contains("--overwrite"),
+                day: args.free_from_str()?,
+                index: args.opt_free_from_str()?,
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                allocs: args.contains("--allocs"),
+                input: args.opt_value_from_str("--input")?,
+                watch: args.contains("--watch"),
+                test: args.contains("--test"),
+                format: args.opt_value_from_str("--format")?,
+            },
+            Some("verify") => AppArguments::Verify {
+                accept: args.contains("--accept"),
+                day: args.opt_free_from_str()?,
+            },
+            Some("stars") => AppArguments::Stars,
+            Some("inputs") => AppArguments::Inputs {
+                action: args.free_from_str()?,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -93,6 +192,19 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parse `--timeout <seconds>`, where `0` disables the timeout, and `--jobs <n>`.
+    fn parse_schedule(args: &mut pico_args::Arguments) -> Result<Schedule, pico_args::Error> {
+        let seconds = args
+            .opt_value_from_str("--timeout")?
+            .unwrap_or(DEFAULT_TIMEOUT_SECS);
+        let jobs: usize = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+
+        Ok(Schedule {
+            timeout: (seconds > 0).then(|| Duration::from_secs(seconds)),
+            jobs: jobs.max(1),
+        })
+    }
 }
 
 fn main() {
@@ -102,43 +214,88 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All { format, schedule } => all::handle(SOLUTIONS, format, schedule),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                export,
+                memory,
+                format,
+                compare,
+                history,
+                schedule,
+            } => match history {
+                Some(day) => time::handle_history(day),
+                None => time::handle(
+                    SOLUTIONS,
+                    day,
+                    all,
+                    time::Options {
+                        memory,
+                        store,
+                        exports: export,
+                    },
+                    format,
+                    compare,
+                    schedule,
+                ),
+            },
+            AppArguments::RunDay { day, is_timed } => run_day::handle(SOLUTIONS, day, is_timed),
             AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::Read { day, part, refresh } => read::handle(day, part, refresh),
+            AppArguments::Example {
+                day,
+                index,
+                list,
+                overwrite,
+            } => example::handle(day, index, list, overwrite),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                let template = template.as_deref().unwrap_or(scaffold::DEFAULT_TEMPLATE);
+                // download first, so the template can refer to the puzzle's title.
                 if download {
                     download::handle(day);
                 }
+                scaffold::handle(day, overwrite, template);
+                if download {
+                    example::handle(day, None, false, false);
+                }
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
+                allocs,
+                input,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
+                format,
+                watch,
+                test,
+            } => {
+                let options = solve::Options {
+                    release,
+                    dhat,
+                    allocs,
+                    input,
+                    submit_part: submit,
+                    format,
                 };
+                if watch {
+                    watch::handle(day, &options, test);
+                } else {
+                    solve::handle(day, &options);
+                }
             }
+            AppArguments::Verify { day, accept } => verify::handle(SOLUTIONS, day, accept),
+            AppArguments::Stars => stars::handle(),
+            AppArguments::Inputs { action } => inputs::handle(action),
+            #[cfg(feature = "today")]
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
@@ -0,0 +1,480 @@
+/// Built-in client for the Advent of Code website.
+/// Downloads inputs and puzzle descriptions and submits answers, authenticated with the session cookie.
+use std::{env, fmt::Display, fs, path::PathBuf, time::Duration};
+
+use crate::template::{html, Day};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+/// Sent unless `AOC_USER_AGENT` is set. Advent of Code asks automated clients to say who to contact about their
+/// traffic, so set it to your repository and contact, e.g. `github.com/you/aoc by you@example.com`.
+static DEFAULT_USER_AGENT: &str =
+    "advent_of_code template (+https://github.com/fspoettel/advent-of-code-rust)";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie in `AOC_SESSION` or the session file.
+    MissingSession,
+    /// `AOC_YEAR` is not set or not a number.
+    MissingYear,
+    /// The server responded with a non-success status code.
+    BadStatus(u16),
+    /// The request could not be sent or the response could not be read.
+    Transport(String),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create the file \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::MissingYear => write!(f, "`AOC_YEAR` is not set to a valid year."),
+            AocClientError::BadStatus(status) => {
+                write!(f, "server responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocClientError {}
+
+impl AocClientError {
+    /// Whether the request may succeed when sent again, e.g. a puzzle that is not served yet right after its unlock.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        match self {
+            AocClientError::BadStatus(status) => *status == 404 || *status >= 500,
+            AocClientError::Transport(_) => true,
+            AocClientError::MissingSession | AocClientError::MissingYear => false,
+        }
+    }
+}
+
+/// Hint given by the server along with a wrong answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnswerHint {
+    TooHigh,
+    TooLow,
+}
+
+/// Verdict of the server for a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect {
+        hint: Option<AnswerHint>,
+        wait: Option<Duration>,
+    },
+    /// An answer was submitted too recently, `wait` is the remaining cooldown.
+    TooRecent {
+        wait: Option<Duration>,
+    },
+    /// The part was already solved or is not unlocked yet.
+    AlreadyCompleted,
+    /// The response could not be classified, contains the response text.
+    Unknown(String),
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmissionOutcome::Incorrect { hint, wait } => {
+                write!(f, "That's not the right answer")?;
+                match hint {
+                    Some(AnswerHint::TooHigh) => write!(f, ", your answer is too high")?,
+                    Some(AnswerHint::TooLow) => write!(f, ", your answer is too low")?,
+                    None => {}
+                }
+                match wait {
+                    Some(wait) => write!(f, ". Wait {} before trying again.", format_wait(*wait)),
+                    None => write!(f, "."),
+                }
+            }
+            SubmissionOutcome::TooRecent { wait } => match wait {
+                Some(wait) => write!(
+                    f,
+                    "You gave an answer too recently, wait {}.",
+                    format_wait(*wait)
+                ),
+                None => write!(f, "You gave an answer too recently."),
+            },
+            SubmissionOutcome::AlreadyCompleted => {
+                write!(f, "This part is already solved or not unlocked yet.")
+            }
+            SubmissionOutcome::Unknown(text) => write!(f, "Unexpected response: {text}"),
+        }
+    }
+}
+
+fn format_wait(wait: Duration) -> String {
+    let secs = wait.as_secs();
+    if secs >= 60 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: u16,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+            agent: build_agent(DEFAULT_USER_AGENT),
+        }
+    }
+
+    /// Send `user_agent` instead of the default user agent.
+    #[must_use]
+    pub fn with_user_agent(self, user_agent: &str) -> Self {
+        Self {
+            agent: build_agent(user_agent.trim()),
+            ..self
+        }
+    }
+
+    /// Configure a client from the environment:
+    ///  1. the session cookie is read from `AOC_SESSION`, falling back to `~/.adventofcode.session`.
+    ///  2. the year is read from `AOC_YEAR`.
+    ///  3. the base URL can be overridden with `AOC_BASE_URL`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = env::var("AOC_SESSION")
+            .ok()
+            .filter(|x| !x.trim().is_empty())
+            .or_else(read_session_file)
+            .ok_or(AocClientError::MissingSession)?;
+
+        let year = env::var("AOC_YEAR")
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .ok_or(AocClientError::MissingYear)?;
+
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let client = Self::new(&base_url, &session, year);
+
+        match env::var("AOC_USER_AGENT") {
+            Ok(user_agent) if !user_agent.trim().is_empty() => {
+                Ok(client.with_user_agent(&user_agent))
+            }
+            _ => Ok(client),
+        }
+    }
+
+    /// Download the puzzle input of a day.
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Download the puzzle description of a day, converted to markdown.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let page = self.get(&self.day_url(day))?;
+        Ok(html::puzzle_to_markdown(&page))
+    }
+
+    /// Submit an answer for a part of a day.
+    pub fn submit(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        let page = read_response(response)?;
+        Ok(parse_submission(&page))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        read_response(response)
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| AocClientError::Transport(e.to_string())),
+        Err(ureq::Error::Status(status, _)) => Err(AocClientError::BadStatus(status)),
+        Err(e) => Err(AocClientError::Transport(e.to_string())),
+    }
+}
+
+fn build_agent(user_agent: &str) -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .user_agent(user_agent)
+        .timeout(Duration::from_secs(30))
+        .build()
+}
+
+fn read_session_file() -> Option<String> {
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    let session = fs::read_to_string(PathBuf::from(home).join(SESSION_FILE_NAME)).ok()?;
+    let session = session.trim().to_string();
+    (!session.is_empty()).then_some(session)
+}
+
+/// Classify the response page of an answer submission.
+fn parse_submission(page: &str) -> SubmissionOutcome {
+    let text = html::to_markdown(
+        page.find("<article")
+            .and_then(|start| {
+                let end = page[start..].find("</article>")?;
+                Some(&page[start..start + end])
+            })
+            .unwrap_or(page),
+    );
+    let text = text.trim().to_string();
+
+    if text.contains("That's the right answer") {
+        SubmissionOutcome::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(AnswerHint::TooHigh)
+        } else if text.contains("too low") {
+            Some(AnswerHint::TooLow)
+        } else {
+            None
+        };
+        SubmissionOutcome::Incorrect {
+            hint,
+            wait: parse_wait(&text),
+        }
+    } else if text.contains("You gave an answer too recently") {
+        SubmissionOutcome::TooRecent {
+            wait: parse_wait(&text),
+        }
+    } else if text.contains("Did you already complete it")
+        || text.contains("You don't seem to be solving the right level")
+    {
+        SubmissionOutcome::AlreadyCompleted
+    } else {
+        SubmissionOutcome::Unknown(text)
+    }
+}
+
+/// Parse the cooldown from phrases like "You have 1m 5s left to wait" or "please wait 5 minutes".
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some(end) = text.find(" left to wait") {
+        let start = text[..end].rfind("You have ")? + "You have ".len();
+        let secs = text[start..end]
+            .split_whitespace()
+            .map(|part| {
+                let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+                let value: u64 = value.parse().ok()?;
+                match unit {
+                    "h" => Some(value * 3600),
+                    "m" => Some(value * 60),
+                    "s" => Some(value),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()?;
+        return Some(Duration::from_secs(secs));
+    }
+
+    let start = text.find("wait ")? + "wait ".len();
+    let mut words = text[start..].split_whitespace();
+    let value = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "five" => 5,
+        "ten" => 10,
+        x => x.parse().ok()?,
+    };
+    let unit = words.next()?;
+    if unit.starts_with("minute") {
+        Some(Duration::from_secs(value * 60))
+    } else if unit.starts_with("second") {
+        Some(Duration::from_secs(value))
+    } else {
+        None
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    use super::{parse_submission, AnswerHint, AocClient, AocClientError, SubmissionOutcome};
+    use crate::day;
+
+    /// Serve a single canned response on a local port, sending the received request back through the channel.
+    fn serve_once(status: &str, body: &str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8_lossy(&body));
+
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            sender.send(request).unwrap();
+        });
+
+        (address, receiver)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (address, requests) = serve_once("200 OK", "1 2\n3 4\n");
+        let client = AocClient::new(&address, "secret\n", 2024);
+
+        assert_eq!(client.input(day!(1)).unwrap(), "1 2\n3 4\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+        assert!(request.contains("advent_of_code template"));
+    }
+
+    #[test]
+    fn sends_custom_user_agent() {
+        let (address, requests) = serve_once("200 OK", "1 2\n");
+        let client = AocClient::new(&address, "secret", 2024)
+            .with_user_agent("github.com/team/aoc by team@example.com\n");
+
+        client.input(day!(1)).unwrap();
+
+        let request = requests.recv().unwrap();
+        assert!(request.contains("github.com/team/aoc by team@example.com\r\n"));
+        assert!(!request.contains("advent_of_code template"));
+    }
+
+    #[test]
+    fn downloads_puzzle_as_markdown() {
+        let (address, _) = serve_once(
+            "200 OK",
+            "<main><article><h2>--- Day 5 ---</h2><p>Hello</p></article></main>",
+        );
+        let client = AocClient::new(&address, "secret", 2024);
+
+        assert_eq!(
+            client.puzzle(day!(5)).unwrap(),
+            "## --- Day 5 ---\n\nHello\n"
+        );
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (address, _) = serve_once("404 Not Found", "not found");
+        let client = AocClient::new(&address, "secret", 2024);
+
+        assert!(matches!(
+            client.input(day!(2)),
+            Err(AocClientError::BadStatus(404))
+        ));
+    }
+
+    #[test]
+    fn flags_transient_errors() {
+        assert!(AocClientError::BadStatus(404).is_transient());
+        assert!(AocClientError::BadStatus(503).is_transient());
+        assert!(AocClientError::Transport("timed out".into()).is_transient());
+        assert!(!AocClientError::BadStatus(400).is_transient());
+        assert!(!AocClientError::MissingSession.is_transient());
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (address, requests) = serve_once(
+            "200 OK",
+            "<main><article><p>That's the right answer! You are one gold star closer.</p></article></main>",
+        );
+        let client = AocClient::new(&address, "secret", 2024);
+
+        assert_eq!(
+            client.submit(day!(3), 2, "1234").unwrap(),
+            SubmissionOutcome::Correct
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2024/day/3/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=1234"));
+    }
+
+    #[test]
+    fn parses_incorrect_answers() {
+        let page = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2024/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>";
+        assert_eq!(
+            parse_submission(page),
+            SubmissionOutcome::Incorrect {
+                hint: Some(AnswerHint::TooHigh),
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+    }
+
+    #[test]
+    fn parses_cooldowns() {
+        let page = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 32s left to wait. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>";
+        assert_eq!(
+            parse_submission(page),
+            SubmissionOutcome::TooRecent {
+                wait: Some(Duration::from_secs(92))
+            }
+        );
+    }
+
+    #[test]
+    fn parses_completed_parts() {
+        let page = "<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>";
+        assert_eq!(parse_submission(page), SubmissionOutcome::AlreadyCompleted);
+    }
+}
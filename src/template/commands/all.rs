@@ -1,5 +1,13 @@
+use std::process;
+
+use crate::template::registry::Solution;
+use crate::template::runner::{OutputFormat, Schedule};
 use crate::template::{all_days, run_multi::run_multi};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(solutions: &[Solution], format: OutputFormat, schedule: Schedule) {
+    let run = run_multi(solutions, &all_days().collect(), false, format, schedule);
+
+    if run.has_failures() {
+        process::exit(1);
+    }
 }
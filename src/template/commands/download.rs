@@ -1,14 +1,78 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use std::time::{Duration, Instant};
+use std::{fs, process, thread};
+
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::Day;
+
+/// How long a puzzle that just unlocked is retried, see `handle_unlocked`.
+const UNLOCK_RETRY_PERIOD: Duration = Duration::from_secs(60);
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(8);
 
 pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    download(day, None);
+}
+
+/// Download a puzzle right after it unlocked. The server may not serve it in the first seconds, or the local clock
+/// may be slightly ahead, so failed requests are retried with a backoff for a while.
+pub fn handle_unlocked(day: Day) {
+    download(day, Some(UNLOCK_RETRY_PERIOD));
+}
+
+fn fetch(client: &AocClient, day: Day) -> Result<(String, String), AocClientError> {
+    let input = client.input(day)?;
+    let puzzle = client.puzzle(day)?;
+    Ok((input, puzzle))
+}
+
+fn download(day: Day, retry_for: Option<Duration>) {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to configure client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let input_path = format!("data/inputs/{day}.txt");
+    let puzzle_path = format!("data/puzzles/{day}.md");
+
+    let deadline = retry_for.map(|period| Instant::now() + period);
+    let mut backoff = INITIAL_BACKOFF;
+
+    let (input, puzzle) = loop {
+        match fetch(&client, day) {
+            Ok(x) => break x,
+            Err(e)
+                if e.is_transient()
+                    && deadline.is_some_and(|deadline| Instant::now() + backoff < deadline) =>
+            {
+                eprintln!(
+                    "Failed to download day {day}: {e} Retrying in {}s.",
+                    backoff.as_secs()
+                );
+                thread::sleep(backoff);
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+            Err(e) => {
+                eprintln!("Failed to download day {day}: {e}");
+                process::exit(1);
+            }
+        }
+    };
+
+    if let Err(e) = fs::write(&input_path, input) {
+        eprintln!("Failed to write input file: {e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = fs::write(&puzzle_path, puzzle) {
+        eprintln!("Failed to write puzzle file: {e}");
         process::exit(1);
-    };
+    }
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
 }
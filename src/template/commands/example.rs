@@ -0,0 +1,96 @@
+use std::{fs, process};
+
+use crate::template::puzzle::Puzzle;
+use crate::template::Day;
+
+/// Number of lines shown per example when listing them.
+const PREVIEW_LINES: usize = 3;
+
+/// Whether a file exists and is not empty, e.g. not one of the placeholders created by `scaffold`.
+fn has_content(path: &str) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+/// Write example `index` of the puzzle and its answers to `data/examples`. The first example is written to
+/// `<day>.txt`, later ones to `<day>-<index + 1>.txt`.
+/// Existing examples or answers are only replaced with `overwrite`, but empty files, e.g. created by `scaffold`, are
+/// always replaced.
+pub fn handle(day: Day, index: Option<usize>, list: bool, overwrite: bool) {
+    let puzzle_path = format!("data/puzzles/{day}.md");
+
+    let puzzle = match fs::read_to_string(&puzzle_path) {
+        Ok(markdown) => Puzzle::parse(&markdown),
+        Err(e) => {
+            eprintln!("Failed to read puzzle file \"{puzzle_path}\": {e}");
+            eprintln!("Type `cargo download {day}` to download the puzzle first.");
+            process::exit(1);
+        }
+    };
+
+    if puzzle.examples.is_empty() {
+        // not fatal, as this also runs as part of `scaffold --download` and `today`.
+        eprintln!("Found no examples in \"{puzzle_path}\".");
+        return;
+    }
+
+    if list {
+        for (i, example) in puzzle.examples.iter().enumerate() {
+            println!("[{i}] part {}:", example.part);
+            for line in example.text.lines().take(PREVIEW_LINES) {
+                println!("    {line}");
+            }
+            if example.text.lines().count() > PREVIEW_LINES {
+                println!("    ...");
+            }
+        }
+        return;
+    }
+
+    let index = index.unwrap_or(0);
+    let Some(answers) = puzzle.expected_answers(index) else {
+        eprintln!(
+            "Day {day} has no example {index}, pick one of 0 to {}.",
+            puzzle.examples.len() - 1
+        );
+        process::exit(1);
+    };
+
+    // NOTE: later examples are added next to the first one, e.g. `01-2.txt` for example 1.
+    let name = match index {
+        0 => day.to_string(),
+        _ => format!("{day}-{}", index + 1),
+    };
+    let example_path = format!("data/examples/{name}.txt");
+    let answers_path = format!("data/examples/{name}.answers");
+
+    if !overwrite {
+        if let Some(path) = [&example_path, &answers_path]
+            .into_iter()
+            .find(|path| has_content(path))
+        {
+            // not fatal, as this also runs as part of `scaffold --download` and `today`.
+            eprintln!("Kept existing file \"{path}\". Use `--overwrite` to replace it.");
+            return;
+        }
+    }
+
+    if let Err(e) = fs::write(&example_path, &puzzle.examples[index].text) {
+        eprintln!("Failed to write example file: {e}");
+        process::exit(1);
+    }
+
+    if let Err(e) = fs::write(&answers_path, answers.to_string()) {
+        eprintln!("Failed to write answers file: {e}");
+        process::exit(1);
+    }
+
+    println!("---");
+    println!("🎄 Successfully wrote example {index} to \"{example_path}\".");
+    for part in [1, 2] {
+        match answers.get(part) {
+            Some(answer) => println!("🎄 Expecting {answer} for part {part}."),
+            None => println!("🎄 Found no answer for part {part}."),
+        }
+    }
+    println!("🎄 Successfully wrote answers to \"{answers_path}\".");
+}
@@ -0,0 +1,148 @@
+use std::{fmt::Display, fs, path::Path, process, str::FromStr};
+
+use crate::template::crypt::{self, Key};
+
+static INPUTS_PATH: &str = "data/inputs";
+
+/// What `inputs` does with the inputs folder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Write an encrypted copy of every input, e.g. `01.txt.enc` for `01.txt`.
+    Encrypt,
+    /// Restore every input from its encrypted copy.
+    Decrypt,
+    /// Print a new random key.
+    Keygen,
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "encrypt" => Ok(Action::Encrypt),
+            "decrypt" => Ok(Action::Decrypt),
+            "keygen" => Ok(Action::Keygen),
+            _ => Err(format!(
+                "unknown action `{s}`, expecting `encrypt`, `decrypt` or `keygen`"
+            )),
+        }
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Encrypt => write!(f, "encrypt"),
+            Action::Decrypt => write!(f, "decrypt"),
+            Action::Keygen => write!(f, "keygen"),
+        }
+    }
+}
+
+/// Names of the files in the inputs folder, skipping hidden files.
+fn input_names() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(INPUTS_PATH)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().is_file())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| !name.starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort_unstable();
+    names
+}
+
+/// Encrypt `name`, unless its encrypted copy is up to date. Re-encrypting would change the copy because of the
+/// random nonce, which would show up as a change in git.
+fn encrypt(key: &Key, name: &str) -> Result<bool, String> {
+    let path = Path::new(INPUTS_PATH).join(name);
+    let encrypted_path = Path::new(INPUTS_PATH).join(format!("{name}.{}", crypt::EXTENSION));
+
+    let plain = fs::read(&path).map_err(|e| e.to_string())?;
+
+    if let Ok(existing) = fs::read(&encrypted_path) {
+        if crypt::decrypt(key, name, &existing).is_ok_and(|x| x == plain) {
+            return Ok(false);
+        }
+    }
+
+    let encrypted = crypt::encrypt(key, name, &plain).map_err(|e| e.to_string())?;
+    fs::write(&encrypted_path, encrypted).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+fn decrypt(key: &Key, encrypted_name: &str) -> Result<bool, String> {
+    let name = encrypted_name
+        .strip_suffix(&format!(".{}", crypt::EXTENSION))
+        .unwrap_or(encrypted_name);
+    let path = Path::new(INPUTS_PATH).join(name);
+
+    let encrypted =
+        fs::read(Path::new(INPUTS_PATH).join(encrypted_name)).map_err(|e| e.to_string())?;
+    let plain = crypt::decrypt(key, name, &encrypted).map_err(|e| e.to_string())?;
+
+    if fs::read(&path).is_ok_and(|x| x == plain) {
+        return Ok(false);
+    }
+
+    fs::write(&path, plain).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+pub fn handle(action: Action) {
+    if action == Action::Keygen {
+        match Key::generate() {
+            Ok(key) => println!("{key}"),
+            Err(e) => {
+                eprintln!("Failed to generate key: {e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let key = match Key::from_env() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("Failed to load key: {e}");
+            process::exit(1);
+        }
+    };
+
+    let suffix = format!(".{}", crypt::EXTENSION);
+    let mut failed = false;
+    let mut changed = 0;
+
+    for name in input_names() {
+        let is_encrypted = name.ends_with(&suffix);
+        let result = match action {
+            Action::Encrypt if !is_encrypted => encrypt(&key, &name),
+            Action::Decrypt if is_encrypted => decrypt(&key, &name),
+            _ => continue,
+        };
+
+        match result {
+            Ok(true) => {
+                changed += 1;
+                println!("🎄 {action}ed \"{INPUTS_PATH}/{name}\".");
+            }
+            Ok(false) => {}
+            Err(e) => {
+                failed = true;
+                eprintln!("Failed to {action} \"{INPUTS_PATH}/{name}\": {e}");
+            }
+        }
+    }
+
+    println!("---");
+    println!("🎄 {changed} files {action}ed, all others are up to date.");
+
+    if failed {
+        process::exit(1);
+    }
+}
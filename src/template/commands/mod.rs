@@ -1,6 +1,14 @@
 pub mod all;
 pub mod download;
+pub mod example;
+pub mod inputs;
 pub mod read;
+pub mod run_day;
 pub mod scaffold;
 pub mod solve;
+pub mod stars;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
+pub mod verify;
+pub mod watch;
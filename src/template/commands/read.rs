@@ -1,15 +1,73 @@
-use std::process;
+use std::{env, fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::aoc_client::AocClient;
+use crate::template::{markdown, puzzle, Day};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Width puzzles are wrapped to if the terminal width is unknown.
+const DEFAULT_WIDTH: usize = 80;
+
+/// Width puzzles are wrapped to at most, as long lines are hard to read.
+const MAX_WIDTH: usize = 100;
+
+fn fetch(day: Day, puzzle_path: &str) -> String {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to configure client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let puzzle = match client.puzzle(day) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            eprintln!("Failed to fetch puzzle for day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = fs::write(puzzle_path, &puzzle) {
+        eprintln!("Failed to write puzzle file: {e}");
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    puzzle
+}
+
+fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(DEFAULT_WIDTH)
+        .min(MAX_WIDTH)
+}
+
+/// Render the puzzle description of a day, or of one of its parts.
+/// The cached description in `data/puzzles` is used unless `refresh` is set or the requested part is missing
+/// from it, e.g. because part two unlocked after it was downloaded.
+pub fn handle(day: Day, part: Option<u8>, refresh: bool) {
+    let puzzle_path = format!("data/puzzles/{day}.md");
+
+    let cached = if refresh {
+        None
+    } else {
+        fs::read_to_string(&puzzle_path).ok()
+    };
+
+    let markdown = match cached {
+        Some(markdown) if puzzle::part(&markdown, part.unwrap_or(1)).is_some() => markdown,
+        _ => fetch(day, &puzzle_path),
+    };
+
+    let text = match part {
+        Some(part) => match puzzle::part(&markdown, part) {
+            Some(text) => text,
+            None => {
+                eprintln!("Part {part} of day {day} is not unlocked yet.");
+                process::exit(1);
+            }
+        },
+        None => &markdown,
     };
+
+    print!("{}", markdown::render(text, terminal_width()));
 }
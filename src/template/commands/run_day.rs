@@ -0,0 +1,34 @@
+use std::process;
+
+use crate::template::registry::{self, Solution};
+use crate::template::runner::{panic_count, OutputFormat, RunOptions};
+use crate::template::{try_read_file, Day};
+
+/// Run a single day in-process, printing JSON records.
+/// This is the entry point of the child processes spawned by `all` and `time`.
+pub fn handle(solutions: &[Solution], day: Day, is_timed: bool) {
+    let Some(solution) = registry::find(solutions, day) else {
+        eprintln!("No solution for day {day}.");
+        process::exit(1);
+    };
+
+    let input = match try_read_file("inputs", day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Could not read input file for day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    solution.run(
+        &input,
+        &RunOptions {
+            is_timed,
+            format: OutputFormat::Json,
+        },
+    );
+
+    if panic_count() > 0 {
+        process::exit(101);
+    }
+}
@@ -1,13 +1,46 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    env,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{has_encrypted_input, puzzle, Day};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Templates that ship with the project, selectable with `--template <name>`.
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "default",
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt")),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "parse",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/parse.txt"
+        )),
+    ),
+    (
+        "u64",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/u64.txt"
+        )),
+    ),
+];
+
+/// Directory of user-defined templates. A template `<name>.txt` in it takes precedence over a built-in one.
+const USER_TEMPLATES_PATH: &str = "templates";
+
+pub const DEFAULT_TEMPLATE: &str = "default";
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -19,15 +52,94 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Create an empty file, unless it already exists. Returns whether the file was created.
+fn create_missing_file(path: &str) -> Result<bool, std::io::Error> {
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn user_template_names() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(USER_TEMPLATES_PATH) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == "txt").then_some(path.file_stem()?.to_str()?.to_string())
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+fn load_template(name: &str) -> Result<String, String> {
+    let user_path = Path::new(USER_TEMPLATES_PATH).join(format!("{name}.txt"));
+    if user_path.exists() {
+        return fs::read_to_string(&user_path)
+            .map_err(|e| format!("could not read \"{}\": {e}", user_path.display()));
+    }
+
+    if let Some((_, template)) = BUILTIN_TEMPLATES.iter().find(|(n, _)| *n == name) {
+        return Ok((*template).to_string());
+    }
+
+    let mut available: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(n, _)| (*n).to_string())
+        .collect();
+    available.extend(user_template_names());
+    available.sort();
+    available.dedup();
+
+    Err(format!(
+        "unknown template `{name}`, available templates: {}.",
+        available.join(", ")
+    ))
+}
+
+/// Values substituted for the placeholders of a template.
+struct Placeholders {
+    day: Day,
+    year: String,
+    title: String,
+}
+
+impl Placeholders {
+    fn new(day: Day) -> Self {
+        let title = fs::read_to_string(format!("data/puzzles/{day}.md"))
+            .ok()
+            .and_then(|markdown| puzzle::title(&markdown).map(str::to_string))
+            .unwrap_or_else(|| format!("Day {}", day.into_inner()));
+
+        Self {
+            day,
+            year: env::var("AOC_YEAR").unwrap_or_default(),
+            title,
+        }
+    }
+
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("%DAY_NUMBER%", &self.day.into_inner().to_string())
+            .replace("%DAY%", &self.day.to_string())
+            .replace("%YEAR%", &self.year)
+            .replace("%TITLE%", &self.title)
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
+pub fn handle(day: Day, overwrite: bool, template: &str) {
+    let template = match load_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
+        }
+    };
+
     let input_path = format!("data/inputs/{day}.txt");
     let example_path = format!("data/examples/{day}.txt");
     let module_path = format!("src/bin/{day}.rs");
@@ -40,11 +152,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(Placeholders::new(day).render(&template).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -54,20 +162,31 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    // NOTE: an empty input file would shadow an encrypted copy of the input, see `try_read_file`.
+    if has_encrypted_input(day) {
+        println!("Using encrypted input file \"{input_path}.enc\"");
+    } else {
+        match create_missing_file(&input_path) {
+            Ok(true) => {
+                println!("Created empty input file \"{}\"", &input_path);
+            }
+            Ok(false) => {
+                println!("Kept existing input file \"{}\"", &input_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create input file: {e}");
+                process::exit(1);
+            }
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
+    match create_missing_file(&example_path) {
+        Ok(true) => {
             println!("Created empty example file \"{}\"", &example_path);
         }
+        Ok(false) => {
+            println!("Kept existing example file \"{}\"", &example_path);
+        }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
             process::exit(1);
@@ -77,3 +196,30 @@ pub fn handle(day: Day, overwrite: bool) {
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{load_template, Placeholders};
+    use crate::day;
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = Placeholders {
+            day: day!(5),
+            year: "2024".into(),
+            title: "Print Queue".into(),
+        };
+        assert_eq!(
+            placeholders.render("solution!(%DAY_NUMBER%); // %DAY% %YEAR% %TITLE%"),
+            "solution!(5); // 05 2024 Print Queue"
+        );
+    }
+
+    #[test]
+    fn loads_builtin_templates() {
+        assert!(load_template("grid").unwrap().contains("%DAY_NUMBER%"));
+        assert!(load_template("missing").is_err());
+    }
+}
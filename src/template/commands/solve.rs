@@ -1,30 +1,63 @@
 use std::process::{Command, Stdio};
 
+use crate::template::runner::OutputFormat;
 use crate::template::Day;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+/// How a solution is built and which arguments are passed to it.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    pub release: bool,
+    pub dhat: bool,
+    pub allocs: bool,
+    pub input: Option<String>,
+    pub submit_part: Option<u8>,
+    pub format: Option<OutputFormat>,
+}
+
+/// Arguments of the `cargo run` invocation that runs the solution of `day`.
+#[must_use]
+pub fn cargo_args(day: Day, options: &Options) -> Vec<String> {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
-    if dhat {
+    if options.dhat {
         cmd_args.extend([
             "--profile".to_string(),
             "dhat".to_string(),
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if options.release {
+            cmd_args.push("--release".to_string());
+        }
+        if options.allocs {
+            cmd_args.extend(["--features".to_string(), "count-allocs".to_string()]);
+        }
     }
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(input) = &options.input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.clone());
+    }
+
+    if let Some(submit_part) = options.submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(format) = options.format {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    cmd_args
+}
+
+pub fn handle(day: Day, options: &Options) {
     let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+        .args(cargo_args(day, options))
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
@@ -0,0 +1,19 @@
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::readme_stars::{self, Stars};
+use crate::template::submissions::Submissions;
+
+pub fn handle() {
+    let stars = Stars::collect(&Answers::read_from_file(), &Submissions::read_from_file());
+
+    match readme_stars::update(&stars) {
+        Ok(()) => {
+            println!("🎄 Updated readme with {} stars.", stars.count());
+        }
+        Err(_) => {
+            eprintln!("Failed to update readme stars.");
+            process::exit(1);
+        }
+    }
+}
@@ -1,16 +1,43 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
+use crate::template::compare;
+use crate::template::export::{self, ExportFormat};
+use crate::template::history::{self, RunMetadata};
+use crate::template::memory;
+use crate::template::registry::Solution;
 use crate::template::run_multi::run_multi;
+use crate::template::runner::{OutputFormat, Schedule};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+/// What a benchmark run measures besides execution time, and where its results are written to.
+pub struct Options {
+    /// Profile the heap usage of every day with dhat.
+    pub memory: bool,
+    /// Store the timings in `data/timings.json` and the readme.
+    pub store: bool,
+    /// Export the stored timings, updated with the results of the run.
+    pub exports: Vec<ExportFormat>,
+}
+
+pub fn handle(
+    solutions: &[Solution],
+    day: Option<Day>,
+    run_all: bool,
+    options: Options,
+    format: OutputFormat,
+    compare_threshold: Option<f64>,
+    schedule: Schedule,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, re-run every day that has stored timings.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,12 +48,43 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let run = run_multi(solutions, &days_to_run, true, format, schedule);
+    let mut timings = run.timings.clone().unwrap();
+
+    if options.memory {
+        profile_memory(&mut timings, format);
+    }
+
+    if let Some(threshold) = compare_threshold {
+        let deltas = compare::compare(&stored_timings, &timings);
+        compare::print(&deltas, threshold);
+
+        if deltas.iter().any(|delta| delta.is_regression(threshold)) {
+            eprintln!("Benchmarks regressed by more than {threshold:.1}%.");
+            process::exit(1);
+        }
+    }
+
+    let merged_timings = stored_timings.merge(&timings);
+
+    if !options.exports.is_empty() {
+        println!();
+    }
+
+    for export_format in options.exports {
+        match export::export(&merged_timings, export_format) {
+            Ok(()) => println!("Exported benchmarks to \"{}\".", export_format.path()),
+            Err(e) => eprintln!("Failed to export benchmarks as {export_format}: {e}"),
+        }
+    }
 
-    if store {
-        let merged_timings = stored_timings.merge(&timings);
+    if options.store {
         merged_timings.store_file().unwrap();
 
+        if let Err(e) = history::append(&timings, &RunMetadata::collect()) {
+            eprintln!("Failed to append to benchmark history: {e}");
+        }
+
         println!();
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
@@ -37,4 +95,43 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if run.has_failures() {
+        process::exit(1);
+    }
+}
+
+/// Run every benched day under dhat and attach its heap usage to its timing.
+fn profile_memory(timings: &mut Timings, format: OutputFormat) {
+    let is_human = format == OutputFormat::Human;
+
+    if is_human {
+        println!();
+        println!("{ANSI_BOLD}Memory{ANSI_RESET}");
+        println!("------");
+    }
+
+    for timing in &mut timings.data {
+        match memory::profile(timing.day) {
+            Ok(stats) => {
+                if is_human {
+                    println!(
+                        "Day {}: peak {} in {} blocks, total {} in {} blocks",
+                        timing.day,
+                        memory::format_bytes(stats.peak_bytes),
+                        stats.peak_blocks,
+                        memory::format_bytes(stats.total_bytes),
+                        stats.total_blocks
+                    );
+                }
+                timing.memory = Some(stats);
+            }
+            Err(e) => eprintln!("Failed to profile heap usage: {e}"),
+        }
+    }
+}
+
+/// Show how the stored benchmarks of a day evolved over time.
+pub fn handle_history(day: Day) {
+    history::print(day, &history::read_for_day(day));
 }
@@ -0,0 +1,81 @@
+use std::io::{stdout, Write};
+use std::{env, process, thread, time::Duration};
+
+use chrono::Utc;
+
+use crate::template::commands::{download, example, read, scaffold};
+use crate::template::{unlocks, Day};
+
+/// Scaffold, download and read the puzzle of the current day.
+/// With `wait`, a countdown to the next unlock is shown first and the puzzle of the unlocked day is used.
+pub fn handle(wait: bool) {
+    let day = if wait {
+        wait_for_unlock()
+    } else {
+        match Day::today() {
+            Some(day) => day,
+            None => {
+                eprintln!(
+                    "`today` command can only be run between the 1st and \
+                    the 25th of december. Please use `scaffold` with a specific day, \
+                    or `today --wait` to wait for the next puzzle."
+                );
+                process::exit(1)
+            }
+        }
+    };
+
+    if wait {
+        download::handle_unlocked(day);
+    } else {
+        download::handle(day);
+    }
+    scaffold::handle(day, false, scaffold::DEFAULT_TEMPLATE);
+    example::handle(day, None, false, false);
+    read::handle(day, None, false);
+}
+
+/// Show a countdown until the next puzzle of the `AOC_YEAR` event unlocks, then record its unlock time.
+fn wait_for_unlock() -> Day {
+    // NOTE: wait for the event that `download` and `read` use, which is not necessarily the one of this year.
+    let Some(year) = env::var("AOC_YEAR").ok().and_then(|x| x.parse().ok()) else {
+        eprintln!("`AOC_YEAR` is not set to a valid year.");
+        process::exit(1);
+    };
+
+    let Some((day, unlock)) = Day::next_unlock(year) else {
+        eprintln!(
+            "All puzzles of {year} are unlocked already. Set `AOC_YEAR` in `.cargo/config.toml` to the year you \
+            want to wait for."
+        );
+        process::exit(1);
+    };
+
+    loop {
+        let millis = unlock.timestamp_millis() - Utc::now().timestamp_millis();
+        if millis <= 0 {
+            break;
+        }
+
+        let seconds = u64::try_from((millis + 999) / 1000).unwrap_or_default();
+        print!(
+            "\rDay {day} unlocks in {} ",
+            unlocks::format_seconds(seconds)
+        );
+        let _ = stdout().flush();
+
+        // wake up on the next full second, so the countdown does not drift.
+        thread::sleep(Duration::from_millis(
+            u64::try_from((millis - 1) % 1000 + 1).unwrap_or(1000),
+        ));
+    }
+
+    println!("\rDay {day} is unlocked!                  ");
+
+    let timestamp = u64::try_from(unlock.timestamp()).unwrap_or_default();
+    if let Err(e) = unlocks::record(day, timestamp) {
+        eprintln!("Failed to record unlock time: {e}");
+    }
+
+    day
+}
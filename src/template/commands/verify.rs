@@ -0,0 +1,118 @@
+use std::{collections::HashSet, process};
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::registry::{self, Solution};
+use crate::template::runner::{panic_count, OutputFormat, RunOptions};
+use crate::template::{all_days, try_read_file, Day, ANSI_BOLD, ANSI_RESET};
+
+/// Run solutions and check their answers against `data/answers.json`.
+/// With `accept`, answers of parts without an accepted answer are recorded.
+/// Exits with an error if a part does not match its accepted answer or no longer produces one, or if a day panics.
+pub fn handle(solutions: &[Solution], day: Option<Day>, accept: bool) {
+    let mut answers = Answers::read_from_file();
+    let options = RunOptions {
+        is_timed: false,
+        format: OutputFormat::Quiet,
+    };
+
+    let days_to_run: HashSet<Day> = day.map_or_else(
+        || solutions.iter().map(|s| s.day).collect(),
+        |day| HashSet::from([day]),
+    );
+
+    let (mut matches, mut mismatches, mut missing, mut failed) = (0, 0, 0, 0);
+    let mut need_space = false;
+
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let Some(solution) = registry::find(solutions, day) else {
+            println!("Not solved.");
+            continue;
+        };
+
+        let Ok(input) = try_read_file("inputs", day) else {
+            println!("No input file.");
+            continue;
+        };
+
+        let panics = panic_count();
+        let results = solution.run(&input, &options);
+        let has_panicked = panic_count() > panics;
+
+        for part in [1, 2] {
+            let result = results.iter().find(|r| r.part == part);
+            let expected = answers.get(day, part);
+
+            // NOTE: parts that are not part of the solution, e.g. with `solution!(1, 1)`, are skipped.
+            if result.is_none() && expected.is_none() {
+                continue;
+            }
+
+            let actual = result.and_then(|r| r.answer.as_deref());
+
+            match Verdict::new(expected, actual) {
+                Verdict::Match => {
+                    matches += 1;
+                    println!("Part {part}: ✔ {}", actual.unwrap_or_default());
+                }
+                Verdict::Mismatch { expected } => {
+                    mismatches += 1;
+                    println!(
+                        "Part {part}: {ANSI_BOLD}✖ {}{ANSI_RESET} (expected {expected})",
+                        actual.unwrap_or_default()
+                    );
+                }
+                Verdict::Regressed { expected } => {
+                    mismatches += 1;
+                    let reason = match result {
+                        Some(result) => result.error.as_deref().unwrap_or("no answer"),
+                        None => "did not finish",
+                    };
+                    println!(
+                        "Part {part}: {ANSI_BOLD}✖ {reason}{ANSI_RESET} (expected {expected})"
+                    );
+                }
+                Verdict::Missing => {
+                    missing += 1;
+                    let answer = actual.unwrap_or_default();
+                    if accept {
+                        answers.set(day, part, answer);
+                        println!("Part {part}: ? {answer} (accepted)");
+                    } else {
+                        println!("Part {part}: ? {answer} (no known answer)");
+                    }
+                }
+                Verdict::Unsolved => {
+                    missing += 1;
+                    println!("Part {part}: ✖ no answer");
+                }
+            }
+        }
+
+        let has_answers = answers.get(day, 1).is_some() || answers.get(day, 2).is_some();
+        if has_panicked || (has_answers && results.is_empty()) {
+            failed += 1;
+            println!("{ANSI_BOLD}✖ Day {day} failed{ANSI_RESET}");
+        }
+    }
+
+    if accept {
+        if let Err(e) = answers.store_file() {
+            eprintln!("Failed to store answers: {e}");
+        }
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Verified:{ANSI_RESET} {matches} matching, {mismatches} mismatching, {missing} missing, {failed} days failed.");
+
+    if mismatches > 0 || failed > 0 {
+        process::exit(1);
+    }
+}
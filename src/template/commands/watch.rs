@@ -0,0 +1,178 @@
+/// Watch mode of `solve`: re-runs a day whenever its source, input or examples change.
+/// Files are polled for changes to their modification time, which works the same on every platform.
+use std::fs;
+use std::path::PathBuf;
+use std::process::{self, Command};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::template::commands::solve;
+use crate::template::crypt;
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Output of a single run of the solution or its tests.
+struct Run {
+    number: usize,
+    output: String,
+    success: bool,
+}
+
+/// Files that trigger a re-run: the source of the day, its input and its examples.
+/// Without `--input`, the encrypted copy of the input is watched as well, as it is used if the input is missing.
+fn watched_files(day: Day, input: Option<&str>) -> Vec<PathBuf> {
+    let mut files = vec![PathBuf::from(format!("src/bin/{day}.rs"))];
+
+    match input {
+        Some(input) => files.push(PathBuf::from(input)),
+        None => {
+            let input = format!("data/inputs/{day}.txt");
+            files.push(PathBuf::from(&input));
+            files.push(PathBuf::from(format!("{input}.{}", crypt::EXTENSION)));
+        }
+    }
+
+    let mut examples: Vec<PathBuf> = fs::read_dir("data/examples")
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| {
+                    entry
+                        .file_name()
+                        .to_str()
+                        .is_some_and(|name| is_example_of(name, day))
+                })
+                .map(|entry| entry.path())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    examples.sort();
+    files.extend(examples);
+    files
+}
+
+/// Examples belong to a day if their name starts with the zero-padded day, e.g. `01.txt` or `01-2.txt`.
+/// Their expected answers, e.g. `01.answers`, are watched too, as the example tests are generated from them.
+fn is_example_of(name: &str, day: Day) -> bool {
+    name.starts_with(&day.to_string()) && (name.ends_with(".txt") || name.ends_with(".answers"))
+}
+
+fn modified_times(files: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>)> {
+    files
+        .iter()
+        .map(|file| {
+            let modified = fs::metadata(file).and_then(|m| m.modified()).ok();
+            (file.clone(), modified)
+        })
+        .collect()
+}
+
+fn run(day: Day, options: &solve::Options, test: bool, number: usize) -> Run {
+    let mut args = if test {
+        let mut args = vec!["test".to_string(), "--bin".to_string(), day.to_string()];
+        if options.release {
+            args.push("--release".to_string());
+        }
+        args
+    } else {
+        solve::cargo_args(day, options)
+    };
+
+    args.insert(1, "--quiet".to_string());
+    args.insert(2, "--color=always".to_string());
+
+    match Command::new("cargo").args(&args).output() {
+        Ok(output) => Run {
+            number,
+            output: format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            ),
+            success: output.status.success(),
+        },
+        Err(e) => Run {
+            number,
+            output: format!("Failed to run cargo: {e}\n"),
+            success: false,
+        },
+    }
+}
+
+fn print_run(title: &str, run: &Run) {
+    let status = if run.success { "✔" } else { "✖" };
+    println!(
+        "{ANSI_BOLD}── {title} #{} {status} ──{ANSI_RESET}",
+        run.number
+    );
+    print!("{}", run.output);
+    if !run.output.ends_with('\n') {
+        println!();
+    }
+}
+
+/// Run the solution of a day, or its tests, and re-run it whenever one of its files changes.
+/// The output of the previous run is kept on screen above the latest one.
+pub fn handle(day: Day, options: &solve::Options, test: bool) {
+    if options.input.as_deref() == Some("-") {
+        eprintln!("Can not watch an input read from stdin, pass a path to --input instead.");
+        process::exit(1);
+    }
+
+    if options.submit_part.is_some() {
+        eprintln!("--watch can not be combined with --submit.");
+        process::exit(1);
+    }
+
+    let mut previous: Option<Run> = None;
+
+    for number in 1.. {
+        let files = watched_files(day, options.input.as_deref());
+        let modified = modified_times(&files);
+
+        let current = run(day, options, test, number);
+
+        print!("{ANSI_CLEAR}");
+        println!(
+            "Watching {} files of day {day}, press Ctrl+C to stop.",
+            files.len()
+        );
+        println!();
+        if let Some(previous) = &previous {
+            print_run("Previous run", previous);
+            println!();
+        }
+        print_run("Run", &current);
+
+        while modified_times(&watched_files(day, options.input.as_deref())) == modified {
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        println!();
+        println!("Change detected, re-running...");
+        previous = Some(current);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::is_example_of;
+    use crate::day;
+
+    #[test]
+    fn matches_examples_of_day() {
+        assert!(is_example_of("01.txt", day!(1)));
+        assert!(is_example_of("01-2.txt", day!(1)));
+        assert!(is_example_of("01.answers", day!(1)));
+        assert!(is_example_of("01-2.answers", day!(1)));
+        assert!(!is_example_of("01.txt.orig", day!(1)));
+        assert!(!is_example_of("11.txt", day!(1)));
+        assert!(!is_example_of("11.answers", day!(1)));
+    }
+}
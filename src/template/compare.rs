@@ -0,0 +1,187 @@
+/// Module that compares fresh benchmark results against the stored timings.
+/// Parts are compared by their median if both runs have stats, otherwise by their stored mean.
+use std::time::Duration;
+
+use crate::template::runner::BenchStats;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Default regression threshold of `cargo time --compare`, in percent.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Change in execution time of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub part: u8,
+    pub old_nanos: f64,
+    pub new_nanos: f64,
+}
+
+impl Delta {
+    /// Relative change in percent, positive values are slowdowns.
+    pub fn percent(&self) -> f64 {
+        (self.new_nanos - self.old_nanos) / self.old_nanos * 100.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent() > threshold
+    }
+}
+
+/// Compare every part present in both `stored` and `new`.
+pub fn compare(stored: &Timings, new: &Timings) -> Vec<Delta> {
+    new.data
+        .iter()
+        .filter_map(|new| {
+            let old = stored.data.iter().find(|t| t.day == new.day)?;
+            Some((old, new))
+        })
+        .flat_map(|(old, new)| {
+            [1, 2].into_iter().filter_map(|part| {
+                let (old_nanos, new_nanos) = part_nanos(old, new, part)?;
+                (old_nanos > 0.0).then_some(Delta {
+                    day: new.day,
+                    part,
+                    old_nanos,
+                    new_nanos,
+                })
+            })
+        })
+        .collect()
+}
+
+fn part_of(timing: &Timing, part: u8) -> (Option<&String>, Option<&BenchStats>) {
+    match part {
+        1 => (timing.part_1.as_ref(), timing.part_1_stats.as_ref()),
+        _ => (timing.part_2.as_ref(), timing.part_2_stats.as_ref()),
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn part_nanos(old: &Timing, new: &Timing, part: u8) -> Option<(f64, f64)> {
+    let (old_str, old_stats) = part_of(old, part);
+    let (new_str, new_stats) = part_of(new, part);
+
+    match (old_stats, new_stats) {
+        (Some(old), Some(new)) => {
+            Some((old.median.as_nanos() as f64, new.median.as_nanos() as f64))
+        }
+        _ => Some((parse_duration(old_str?)?, parse_duration(new_str?)?)),
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Parse a duration as formatted by `Debug`, e.g. `74.13µs`, to nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s.trim() {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+/// Print a line per compared part, highlighting regressions beyond `threshold`.
+pub fn print(deltas: &[Delta], threshold: f64) {
+    println!();
+    println!(
+        "{ANSI_BOLD}Comparison{ANSI_RESET} {ANSI_ITALIC}(threshold: {threshold:.1}%){ANSI_RESET}"
+    );
+    println!("------");
+
+    if deltas.is_empty() {
+        println!("No stored timings to compare against.");
+        return;
+    }
+
+    for delta in deltas {
+        let line = format!(
+            "Day {} Part {}: {} → {} ({:+.1}%)",
+            delta.day,
+            delta.part,
+            format_nanos(delta.old_nanos),
+            format_nanos(delta.new_nanos),
+            delta.percent()
+        );
+
+        if delta.is_regression(threshold) {
+            println!("{ANSI_BOLD}{line} regression{ANSI_RESET}");
+        } else {
+            println!("{line}");
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{compare, parse_duration, Delta};
+    use crate::day;
+    use crate::template::fixtures::{stats, timing};
+    use crate::template::timings::Timings;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("74.5ns"), Some(74.5));
+        assert_eq!(parse_duration("74.5µs"), Some(74_500.0));
+        assert_eq!(parse_duration("2ms"), Some(2_000_000.0));
+        assert_eq!(parse_duration("1.5s"), Some(1_500_000_000.0));
+        assert_eq!(parse_duration("-"), None);
+    }
+
+    #[test]
+    fn compares_overlapping_days() {
+        let stored = Timings {
+            data: vec![
+                timing(day!(1), Some("10.0ms"), Some("20.0ms")),
+                timing(day!(2), Some("1.0ms"), None),
+            ],
+        };
+        let new = Timings {
+            data: vec![
+                timing(day!(1), Some("12.0ms"), Some("10.0ms")),
+                timing(day!(3), Some("1.0ms"), None),
+            ],
+        };
+
+        let deltas = compare(&stored, &new);
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[0].day, day!(1));
+        assert_eq!(deltas[0].part, 1);
+        assert!((deltas[0].percent() - 20.0).abs() < 1e-9);
+        assert!((deltas[1].percent() + 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn prefers_medians() {
+        let mut old = timing(day!(1), Some("10.0ms"), None);
+        old.part_1_stats = Some(stats(1_000));
+        let mut new = timing(day!(1), Some("20.0ms"), None);
+        new.part_1_stats = Some(stats(1_050));
+
+        let deltas = compare(&Timings { data: vec![old] }, &Timings { data: vec![new] });
+        assert_eq!(deltas[0].old_nanos, 1_000.0);
+        assert_eq!(deltas[0].new_nanos, 1_050.0);
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let delta = Delta {
+            day: day!(1),
+            part: 1,
+            old_nanos: 100.0,
+            new_nanos: 115.0,
+        };
+        assert_eq!(delta.is_regression(10.0), true);
+        assert_eq!(delta.is_regression(20.0), false);
+    }
+}
@@ -0,0 +1,203 @@
+/// Encryption of puzzle inputs, so they can be committed without publishing them.
+/// Files are encrypted with ChaCha20-Poly1305 and a key shared by the team. An encrypted file starts with a header
+/// and a random nonce, followed by the ciphertext. The name of the plain file is authenticated as well, so an
+/// encrypted input can not be passed off as the input of another day.
+use std::{env, fmt::Display, fs, str::FromStr};
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// Extension appended to the name of an encrypted file, e.g. `01.txt.enc`.
+pub const EXTENSION: &str = "enc";
+
+static KEY_ENV: &str = "AOC_INPUTS_KEY";
+static KEY_FILE_PATH: &str = ".aoc-inputs.key";
+static HEADER: &[u8] = b"aoc-enc-v1\n";
+
+const KEY_LEN: usize = 32;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CryptError {
+    /// No key in `AOC_INPUTS_KEY` or the key file.
+    MissingKey,
+    /// The key is not 64 hex digits.
+    InvalidKey,
+    /// The file is not an encrypted input.
+    Malformed,
+    /// The file was encrypted with another key or was modified.
+    Decrypt,
+    /// No secure random numbers available for the nonce.
+    Random,
+}
+
+impl Display for CryptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptError::MissingKey => write!(
+                f,
+                "no inputs key found. Set `{KEY_ENV}` or create the file \"{KEY_FILE_PATH}\"."
+            ),
+            CryptError::InvalidKey => {
+                write!(f, "the inputs key is not {} hex digits.", 2 * KEY_LEN)
+            }
+            CryptError::Malformed => write!(f, "not an encrypted input."),
+            CryptError::Decrypt => write!(
+                f,
+                "could not decrypt, the file was encrypted with another key or is corrupted."
+            ),
+            CryptError::Random => write!(f, "could not generate a nonce."),
+        }
+    }
+}
+
+impl std::error::Error for CryptError {}
+
+/// Key the inputs are encrypted with, written as hex digits.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Key([u8; KEY_LEN]);
+
+impl Key {
+    /// Generate a random key.
+    pub fn generate() -> Result<Self, CryptError> {
+        let mut bytes = [0; KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| CryptError::Random)?;
+        Ok(Self(bytes))
+    }
+
+    /// Read the key from `AOC_INPUTS_KEY`, falling back to the key file in the repository root.
+    pub fn from_env() -> Result<Self, CryptError> {
+        env::var(KEY_ENV)
+            .ok()
+            .filter(|x| !x.trim().is_empty())
+            .or_else(|| fs::read_to_string(KEY_FILE_PATH).ok())
+            .ok_or(CryptError::MissingKey)?
+            .parse()
+    }
+
+    fn aead_key(&self) -> Result<LessSafeKey, CryptError> {
+        let key =
+            UnboundKey::new(&CHACHA20_POLY1305, &self.0).map_err(|_| CryptError::InvalidKey)?;
+        Ok(LessSafeKey::new(key))
+    }
+}
+
+impl FromStr for Key {
+    type Err = CryptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.len() != 2 * KEY_LEN || !s.is_ascii() {
+            return Err(CryptError::InvalidKey);
+        }
+
+        let mut bytes = [0; KEY_LEN];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte =
+                u8::from_str_radix(&s[2 * i..2 * i + 2], 16).map_err(|_| CryptError::InvalidKey)?;
+        }
+
+        Ok(Self(bytes))
+    }
+}
+
+/// Keeps the key out of debug output.
+impl std::fmt::Debug for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Key(..)")
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Encrypt the contents of the file `name`.
+pub fn encrypt(key: &Key, name: &str, plain: &[u8]) -> Result<Vec<u8>, CryptError> {
+    let mut nonce = [0; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| CryptError::Random)?;
+
+    let mut ciphertext = plain.to_vec();
+    key.aead_key()?
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(name.as_bytes()),
+            &mut ciphertext,
+        )
+        .map_err(|_| CryptError::Malformed)?;
+
+    Ok([HEADER, &nonce, &ciphertext].concat())
+}
+
+/// Decrypt the contents of the file `name`, which were encrypted with `encrypt`.
+pub fn decrypt(key: &Key, name: &str, data: &[u8]) -> Result<Vec<u8>, CryptError> {
+    let data = data.strip_prefix(HEADER).ok_or(CryptError::Malformed)?;
+    if data.len() < NONCE_LEN {
+        return Err(CryptError::Malformed);
+    }
+
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| CryptError::Malformed)?;
+
+    let mut in_out = ciphertext.to_vec();
+    let plain = key
+        .aead_key()?
+        .open_in_place(nonce, Aad::from(name.as_bytes()), &mut in_out)
+        .map_err(|_| CryptError::Decrypt)?;
+
+    Ok(plain.to_vec())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{decrypt, encrypt, CryptError, Key};
+
+    fn key(digit: char) -> Key {
+        digit.to_string().repeat(64).parse().unwrap()
+    }
+
+    #[test]
+    fn parses_keys() {
+        let key = Key::generate().unwrap();
+        assert_eq!(key.to_string().parse::<Key>(), Ok(key));
+        assert_eq!("abc".parse::<Key>(), Err(CryptError::InvalidKey));
+        assert_eq!("g".repeat(64).parse::<Key>(), Err(CryptError::InvalidKey));
+    }
+
+    #[test]
+    fn roundtrips_inputs() {
+        let encrypted = encrypt(&key('a'), "01.txt", b"3   4\n4   3\n").unwrap();
+        assert!(!encrypted.windows(5).any(|w| w == b"3   4"));
+        assert_eq!(
+            decrypt(&key('a'), "01.txt", &encrypted),
+            Ok(b"3   4\n4   3\n".to_vec())
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_key_or_name() {
+        let encrypted = encrypt(&key('a'), "01.txt", b"42").unwrap();
+        assert_eq!(
+            decrypt(&key('b'), "01.txt", &encrypted),
+            Err(CryptError::Decrypt)
+        );
+        assert_eq!(
+            decrypt(&key('a'), "02.txt", &encrypted),
+            Err(CryptError::Decrypt)
+        );
+        assert_eq!(
+            decrypt(&key('a'), "01.txt", b"42"),
+            Err(CryptError::Malformed)
+        );
+    }
+}
@@ -3,7 +3,7 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
@@ -55,6 +55,27 @@ impl Day {
             None
         }
     }
+
+    /// Returns the next day of the event in `year` to unlock and the time it unlocks at, which is midnight at the
+    /// server's UTC offset. Returns `None` if all days of that event are already unlocked.
+    pub fn next_unlock(year: i32) -> Option<(Self, DateTime<FixedOffset>)> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let now = Utc::now().with_timezone(&offset);
+        let tomorrow = now.date_naive().succ_opt()?;
+        let first = NaiveDate::from_ymd_opt(year, 12, 1)?;
+
+        let date = tomorrow.max(first);
+        if date.year() != year || date.month() != 12 || date.day() > 25 {
+            return None;
+        }
+
+        let unlock = date
+            .and_hms_opt(0, 0, 0)?
+            .and_local_timezone(offset)
+            .single()?;
+
+        Some((Self::new(u8::try_from(date.day()).ok()?)?, unlock))
+    }
 }
 
 impl Display for Day {
@@ -152,7 +173,7 @@ macro_rules! day {
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
     use super::{all_days, Day};
 
@@ -0,0 +1,158 @@
+/// Expected answers of the examples in `data/examples`.
+/// An example `<name>.txt` can be accompanied by a file `<name>.answers` that lists what its parts should return:
+///
+/// ```text
+/// part_one: 11
+/// part_two: 31
+/// ```
+///
+/// Either part can be left out, empty lines and lines starting with `#` are ignored.
+/// The `solution!` macro generates one test per answers file that checks the solution against its example.
+use std::{env, fmt::Display, fs, str::FromStr};
+
+use crate::template::registry::Solution;
+use crate::template::runner::{OutputFormat, RunOptions};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExpectedAnswers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl ExpectedAnswers {
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ExpectedAnswers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(answer) = &self.part_one {
+            writeln!(f, "part_one: {answer}")?;
+        }
+        if let Some(answer) = &self.part_two {
+            writeln!(f, "part_two: {answer}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ExpectedAnswers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = ExpectedAnswers::default();
+
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once(':')
+                .ok_or(format!("expected `part_one: <answer>`, found `{line}`."))?;
+
+            let value = Some(value.trim().to_string());
+            match key.trim() {
+                "part_one" => answers.part_one = value,
+                "part_two" => answers.part_two = value,
+                key => return Err(format!("unknown part `{key}`.")),
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+/// Run `solution` against the example `name` and assert that its parts return the expected answers.
+///
+/// # Panics
+///
+/// If the example or its answers can not be read, or a part does not return the expected answer.
+pub fn check(solution: &Solution, name: &str) {
+    let read = |extension: &str| {
+        let path = env::current_dir()
+            .unwrap()
+            .join("data")
+            .join("examples")
+            .join(format!("{name}.{extension}"));
+        fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("could not read \"{}\": {e}", path.display()))
+    };
+
+    let expected: ExpectedAnswers = read("answers")
+        .parse()
+        .unwrap_or_else(|e| panic!("invalid answers for example `{name}`: {e}"));
+
+    let results = solution.run(
+        &read("txt"),
+        &RunOptions {
+            is_timed: false,
+            format: OutputFormat::Quiet,
+        },
+    );
+
+    for part in [1, 2] {
+        let Some(expected) = expected.get(part) else {
+            continue;
+        };
+
+        let result = results.iter().find(|result| result.part == part);
+
+        if let Some(error) = result.and_then(|result| result.error.as_deref()) {
+            panic!("part {part} of example `{name}` returned an error: {error}");
+        }
+
+        let actual = result.and_then(|result| result.answer.as_deref());
+
+        assert_eq!(
+            actual,
+            Some(expected),
+            "unexpected answer for part {part} of example `{name}`"
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::ExpectedAnswers;
+
+    #[test]
+    fn parses_answers() {
+        let answers: ExpectedAnswers = "# day 1\npart_one: 11\n\n  part_two:  a:b \n"
+            .parse()
+            .unwrap();
+        assert_eq!(answers.get(1), Some("11"));
+        assert_eq!(answers.get(2), Some("a:b"));
+    }
+
+    #[test]
+    fn allows_missing_parts() {
+        let answers: ExpectedAnswers = "part_two: 4".parse().unwrap();
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.get(2), Some("4"));
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let answers = ExpectedAnswers {
+            part_one: None,
+            part_two: Some("abc".into()),
+        };
+        assert_eq!(answers.to_string(), "part_two: abc\n");
+        assert_eq!(answers.to_string().parse::<ExpectedAnswers>(), Ok(answers));
+    }
+
+    #[test]
+    fn rejects_malformed_answers() {
+        assert!("part_one 11".parse::<ExpectedAnswers>().is_err());
+        assert!("part_three: 11".parse::<ExpectedAnswers>().is_err());
+    }
+}
@@ -0,0 +1,306 @@
+/// Module that exports benchmark results to files, for use outside of the readme table.
+/// Every part (and parse step) of every day becomes one record, with its duration in nanoseconds.
+use std::{collections::HashMap, fmt::Display, fs, io, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::compare::parse_duration;
+use crate::template::runner::{BenchStats, PARSE_STEP};
+use crate::template::timings::Timings;
+use crate::template::Day;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    /// A bar chart of the parts of every day, on a log scale.
+    Svg,
+}
+
+impl ExportFormat {
+    /// Path the export is written to.
+    #[must_use]
+    pub fn path(self) -> String {
+        format!("./data/benchmarks.{self}")
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            "svg" => Ok(ExportFormat::Svg),
+            _ => Err(format!(
+                "unknown export format `{s}`, expecting `csv`, `json` or `svg`"
+            )),
+        }
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Svg => "svg",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// Benchmark result of a single part.
+#[derive(Clone, Debug, PartialEq)]
+struct Record<'a> {
+    day: Day,
+    /// `0` for the parse step.
+    part: u8,
+    duration: &'a str,
+    nanos: f64,
+    stats: Option<BenchStats>,
+}
+
+fn records(timings: &Timings) -> Vec<Record<'_>> {
+    timings
+        .data
+        .iter()
+        .flat_map(|timing| {
+            [
+                (PARSE_STEP, &timing.parse, timing.parse_stats),
+                (1, &timing.part_1, timing.part_1_stats),
+                (2, &timing.part_2, timing.part_2_stats),
+            ]
+            .into_iter()
+            .filter_map(|(part, duration, stats)| {
+                let duration = duration.as_deref()?;
+                Some(Record {
+                    day: timing.day,
+                    part,
+                    duration,
+                    nanos: parse_duration(duration)?,
+                    stats,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Write `timings` to the file of `format`.
+pub fn export(timings: &Timings, format: ExportFormat) -> Result<(), io::Error> {
+    let records = records(timings);
+    let content = match format {
+        ExportFormat::Csv => to_csv(&records),
+        ExportFormat::Json => JsonValue::Array(records.iter().map(JsonValue::from).collect())
+            .format()
+            .map_err(io::Error::other)?,
+        ExportFormat::Svg => to_svg(&records),
+    };
+    fs::write(format.path(), content)
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn to_csv(records: &[Record]) -> String {
+    let mut lines = vec![
+        "day,part,duration,nanos,mean_nanos,median_nanos,min_nanos,max_nanos,stddev_nanos,p95_nanos,outliers"
+            .to_string(),
+    ];
+
+    for record in records {
+        let stats = match record.stats {
+            Some(stats) => {
+                let nanos = [
+                    stats.mean,
+                    stats.median,
+                    stats.min,
+                    stats.max,
+                    stats.stddev,
+                    stats.p95,
+                ]
+                .map(|x| x.as_nanos().to_string());
+                format!("{},{}", nanos.join(","), stats.outliers)
+            }
+            None => ",,,,,,".into(),
+        };
+
+        lines.push(format!(
+            "{},{},{},{},{stats}",
+            record.day.into_inner(),
+            record.part,
+            record.duration,
+            record.nanos
+        ));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+impl From<&Record<'_>> for JsonValue {
+    fn from(value: &Record) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "day".into(),
+            JsonValue::Number(f64::from(value.day.into_inner())),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("duration".into(), JsonValue::String(value.duration.into()));
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(stats) => JsonValue::from(stats),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+const CHART_WIDTH: f64 = 720.0;
+const LABEL_WIDTH: f64 = 70.0;
+const VALUE_WIDTH: f64 = 80.0;
+const HEADER_HEIGHT: f64 = 50.0;
+const BAR_HEIGHT: f64 = 12.0;
+const DAY_GAP: f64 = 8.0;
+const PART_COLORS: [&str; 2] = ["#4e79a7", "#f28e2b"];
+const DECADE_LABELS: [&str; 10] = [
+    "1ns", "10ns", "100ns", "1µs", "10µs", "100µs", "1ms", "10ms", "100ms", "1s",
+];
+
+/// Render the parts of every day as horizontal bars on a log scale.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_svg(records: &[Record]) -> String {
+    let parts: Vec<&Record> = records.iter().filter(|r| r.part != PARSE_STEP).collect();
+    let mut days: Vec<Day> = parts.iter().map(|r| r.day).collect();
+    days.dedup();
+
+    // the axis spans whole decades around all values, at least 1ns.
+    let log = |nanos: f64| nanos.max(1.0).log10();
+    let low = parts.iter().map(|r| log(r.nanos)).fold(f64::MAX, f64::min);
+    let high = parts.iter().map(|r| log(r.nanos)).fold(0.0, f64::max);
+    let low = if parts.is_empty() { 0.0 } else { low.floor() };
+    let high = high.ceil().max(low + 1.0);
+    let x = |nanos: f64| LABEL_WIDTH + (log(nanos) - low) / (high - low) * CHART_WIDTH;
+
+    let day_height = 2.0 * BAR_HEIGHT + DAY_GAP;
+    let width = LABEL_WIDTH + CHART_WIDTH + VALUE_WIDTH;
+    let height = HEADER_HEIGHT + day_height * days.len() as f64;
+
+    let mut svg = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" font-family="sans-serif" font-size="11">"#
+        ),
+        format!(r##"<rect width="{width}" height="{height}" fill="#ffffff"/>"##),
+    ];
+
+    for (i, color) in PART_COLORS.iter().enumerate() {
+        let legend_x = LABEL_WIDTH + 70.0 * i as f64;
+        svg.push(format!(
+            r#"<rect x="{legend_x}" y="6" width="10" height="10" fill="{color}"/><text x="{}" y="15">Part {}</text>"#,
+            legend_x + 14.0,
+            i + 1
+        ));
+    }
+
+    for decade in low as usize..=high as usize {
+        let grid_x = x(10_f64.powi(decade as i32));
+        let label = DECADE_LABELS.get(decade).copied().unwrap_or("");
+        svg.push(format!(
+            r##"<line x1="{grid_x:.1}" y1="{}" x2="{grid_x:.1}" y2="{height}" stroke="#dddddd"/><text x="{grid_x:.1}" y="{}" text-anchor="middle">{label}</text>"##,
+            HEADER_HEIGHT - 12.0,
+            HEADER_HEIGHT - 16.0
+        ));
+    }
+
+    for (row, day) in days.iter().enumerate() {
+        let top = HEADER_HEIGHT + day_height * row as f64;
+        svg.push(format!(
+            r#"<text x="{}" y="{}" text-anchor="end">Day {}</text>"#,
+            LABEL_WIDTH - 8.0,
+            top + BAR_HEIGHT + 4.0,
+            day.into_inner()
+        ));
+
+        for record in parts.iter().filter(|r| r.day == *day) {
+            let bar_top = top + BAR_HEIGHT * f64::from(record.part - 1);
+            let bar_end = x(record.nanos);
+            svg.push(format!(
+                r#"<rect x="{LABEL_WIDTH}" y="{bar_top}" width="{:.1}" height="{}" fill="{}"/><text x="{:.1}" y="{}">{}</text>"#,
+                (bar_end - LABEL_WIDTH).max(1.0),
+                BAR_HEIGHT - 2.0,
+                PART_COLORS[usize::from(record.part - 1)],
+                bar_end + 4.0,
+                bar_top + BAR_HEIGHT - 3.0,
+                record.duration
+            ));
+        }
+    }
+
+    svg.push("</svg>".into());
+    svg.push(String::new());
+    svg.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{records, to_csv, to_svg, ExportFormat};
+    use crate::day;
+    use crate::template::fixtures::timing;
+    use crate::template::timings::{Timing, Timings};
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    parse: Some("100.0ns".into()),
+                    total_nanos: 2_510_100.0,
+                    ..timing(day!(1), Some("10.0µs"), Some("2.5ms"))
+                },
+                Timing {
+                    total_nanos: 50.0,
+                    ..timing(day!(3), Some("50.0ns"), None)
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("svg".parse::<ExportFormat>(), Ok(ExportFormat::Svg));
+        assert!("png".parse::<ExportFormat>().is_err());
+        assert_eq!(ExportFormat::Csv.path(), "./data/benchmarks.csv");
+    }
+
+    #[test]
+    fn exports_csv() {
+        let timings = get_mock_timings();
+        let csv = to_csv(&records(&timings));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[1], "1,0,100.0ns,100,,,,,,,");
+        assert_eq!(lines[3], "1,2,2.5ms,2500000,,,,,,,");
+        assert_eq!(lines[4], "3,1,50.0ns,50,,,,,,,");
+    }
+
+    #[test]
+    fn exports_svg() {
+        let timings = get_mock_timings();
+        let svg = to_svg(&records(&timings));
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("fill=\"#4e79a7\"").count(), 3);
+        assert_eq!(svg.matches("fill=\"#f28e2b\"").count(), 2);
+        assert!(svg.contains(">Day 3</text>"));
+        // 50ns to 2.5ms spans the decades from 10ns to 10ms.
+        assert!(svg.contains(">10ns</text>") && svg.contains(">10ms</text>"));
+        assert!(!svg.contains(">1ns</text>") && !svg.contains(">100ms</text>"));
+    }
+}
@@ -0,0 +1,45 @@
+/// Fixtures shared by the tests of the template modules.
+use std::time::Duration;
+
+use crate::day;
+use crate::template::runner::{BenchStats, PartResult};
+use crate::template::timings::Timing;
+use crate::template::Day;
+
+/// Timing of a day with the given part durations, e.g. `Some("1.0ms")`.
+pub fn timing(day: Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+    Timing {
+        day,
+        part_1: part_1.map(String::from),
+        part_2: part_2.map(String::from),
+        ..Default::default()
+    }
+}
+
+/// Bench statistics of samples that all took `median` nanoseconds.
+pub fn stats(median: u64) -> BenchStats {
+    let median = Duration::from_nanos(median);
+    BenchStats {
+        mean: median,
+        median,
+        min: median,
+        max: median,
+        stddev: Duration::ZERO,
+        p95: median,
+        outliers: 0,
+    }
+}
+
+/// Result of a single execution of a part of day 1 that took `nanos` nanoseconds.
+pub fn part_result(part: u8, answer: Option<&str>, nanos: u64) -> PartResult {
+    PartResult {
+        day: day!(1),
+        part,
+        answer: answer.map(String::from),
+        error: None,
+        duration: Duration::from_nanos(nanos),
+        samples: 1,
+        stats: None,
+        allocs: None,
+    }
+}
@@ -0,0 +1,310 @@
+/// Module that keeps an append-only history of stored benchmarks.
+/// Every `cargo time --store` appends one JSON line per benched day, together with metadata about the run.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// Describes the environment a benchmark was recorded in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunMetadata {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub commit: Option<String>,
+    pub rustc: Option<String>,
+    pub cpu: Option<String>,
+}
+
+impl RunMetadata {
+    /// Collect metadata for the current run. Values that can not be determined are left empty.
+    pub fn collect() -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|x| x.as_secs())
+                .unwrap_or_default(),
+            commit: git_commit(),
+            rustc: command_output("rustc", &["--version"]),
+            cpu: cpu_model(),
+        }
+    }
+}
+
+/// A single stored benchmark of a day.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub metadata: RunMetadata,
+    pub timing: Timing,
+}
+
+/// Append the timings of a run to the history file.
+pub fn append(timings: &Timings, metadata: &RunMetadata) -> Result<(), io::Error> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?;
+
+    for timing in &timings.data {
+        let entry = HistoryEntry {
+            metadata: metadata.clone(),
+            timing: timing.clone(),
+        };
+        let line = JsonValue::from(&entry)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        writeln!(file, "{line}")?;
+    }
+
+    Ok(())
+}
+
+/// Read all history entries of a day, oldest first. Malformed lines are skipped.
+pub fn read_for_day(day: Day) -> Vec<HistoryEntry> {
+    fs::read_to_string(HISTORY_FILE_PATH)
+        .map(|contents| parse_entries(&contents, day))
+        .unwrap_or_default()
+}
+
+fn parse_entries(contents: &str, day: Day) -> Vec<HistoryEntry> {
+    let mut entries: Vec<HistoryEntry> = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let json = JsonValue::from_str(line).ok()?;
+            HistoryEntry::try_from(&json).ok()
+        })
+        .filter(|entry| entry.timing.day == day)
+        .collect();
+
+    entries.sort_by_key(|entry| entry.metadata.timestamp);
+    entries
+}
+
+/// Print how the parts of a day evolved over time.
+pub fn print(day: Day, entries: &[HistoryEntry]) {
+    println!("{ANSI_BOLD}Day {day} history{ANSI_RESET}");
+    println!("------");
+
+    if entries.is_empty() {
+        println!("No stored benchmarks.");
+        return;
+    }
+
+    for entry in entries {
+        let metadata = &entry.metadata;
+        println!(
+            "{} {} | Part 1: {} | Part 2: {} {ANSI_ITALIC}({}, {}){ANSI_RESET}",
+            format_timestamp(metadata.timestamp),
+            metadata.commit.as_deref().unwrap_or("-"),
+            entry.timing.part_1.as_deref().unwrap_or("-"),
+            entry.timing.part_2.as_deref().unwrap_or("-"),
+            metadata.rustc.as_deref().unwrap_or("unknown rustc"),
+            metadata.cpu.as_deref().unwrap_or("unknown cpu"),
+        );
+    }
+}
+
+/// Format a unix timestamp as `YYYY-MM-DD HH:MM` in UTC.
+fn format_timestamp(timestamp: u64) -> String {
+    let time = Duration::from_secs(timestamp);
+    let days = time.as_secs() / 86_400;
+    let seconds = time.as_secs() % 86_400;
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60
+    )
+}
+
+/// Convert days since the unix epoch to a (year, month, day) date.
+/// see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+pub fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+fn command_output(command: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(command).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!stdout.is_empty()).then_some(stdout)
+}
+
+fn git_commit() -> Option<String> {
+    let commit = command_output("git", &["rev-parse", "--short", "HEAD"])?;
+    let is_dirty = command_output("git", &["status", "--porcelain"]).is_some();
+    Some(if is_dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
+
+fn cpu_model() -> Option<String> {
+    fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| parse_cpu_model(&cpuinfo))
+        .or_else(|| command_output("sysctl", &["-n", "machdep.cpu.brand_string"]))
+        .or_else(|| std::env::var("PROCESSOR_IDENTIFIER").ok())
+}
+
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo
+        .lines()
+        .find(|line| line.starts_with("model name"))
+        .and_then(|line| line.split(':').nth(1))
+        .map(|model| model.trim().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_string(value: Option<&String>) -> JsonValue {
+    match value {
+        Some(x) => JsonValue::String(x.clone()),
+        None => JsonValue::Null,
+    }
+}
+
+impl From<&HistoryEntry> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let metadata = &value.metadata;
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(metadata.timestamp as f64),
+        );
+        map.insert("commit".into(), optional_string(metadata.commit.as_ref()));
+        map.insert("rustc".into(), optional_string(metadata.rustc.as_ref()));
+        map.insert("cpu".into(), optional_string(metadata.cpu.as_ref()));
+        map.insert("timing".into(), JsonValue::from(&value.timing));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        let timing = json
+            .get("timing")
+            .ok_or("Expected entry.timing to be present.")
+            .map(Timing::try_from)??;
+
+        Ok(HistoryEntry {
+            metadata: RunMetadata {
+                timestamp: timestamp as u64,
+                commit: string("commit"),
+                rustc: string("rustc"),
+                cpu: string("cpu"),
+            },
+            timing,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{
+        civil_from_days, format_timestamp, parse_cpu_model, parse_entries, HistoryEntry,
+        RunMetadata,
+    };
+    use crate::{day, template::timings::Timing};
+
+    fn entry(day: crate::template::Day, timestamp: u64, part_1: &str) -> HistoryEntry {
+        HistoryEntry {
+            metadata: RunMetadata {
+                timestamp,
+                commit: Some("abc1234".into()),
+                rustc: Some("rustc 1.83.0".into()),
+                cpu: None,
+            },
+            timing: Timing {
+                day,
+                part_1: Some(part_1.into()),
+                total_nanos: 1_000_f64,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn to_line(entry: &HistoryEntry) -> String {
+        JsonValue::from(entry).stringify().unwrap()
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(format_timestamp(1_733_011_200), "2024-12-01 00:00");
+        assert_eq!(format_timestamp(1_735_084_800 + 3_660), "2024-12-25 01:01");
+    }
+
+    #[test]
+    fn parses_cpu_model() {
+        let cpuinfo =
+            "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel name\t: Intel(R) Core(TM) i7\n";
+        assert_eq!(
+            parse_cpu_model(cpuinfo),
+            Some("Intel(R) Core(TM) i7".into())
+        );
+        assert_eq!(parse_cpu_model("processor\t: 0"), None);
+    }
+
+    #[test]
+    fn filters_and_sorts_entries() {
+        let contents = [
+            to_line(&entry(day!(1), 20, "2ms")),
+            to_line(&entry(day!(2), 15, "5ms")),
+            "not json".to_string(),
+            to_line(&entry(day!(1), 10, "1ms")),
+        ]
+        .join("\n");
+
+        let entries = parse_entries(&contents, day!(1));
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].timing.part_1, Some("1ms".into()));
+        assert_eq!(entries[1].timing.part_1, Some("2ms".into()));
+        assert_eq!(entries[0].metadata.commit, Some("abc1234".into()));
+        assert_eq!(entries[0].metadata.cpu, None);
+    }
+}
@@ -0,0 +1,250 @@
+/// Minimal HTML to markdown conversion for Advent of Code puzzle pages.
+/// Only the handful of elements used in puzzle descriptions are supported, everything else is reduced to its text.
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open { name: String, href: Option<String> },
+    Close(String),
+    Text(String),
+}
+
+/// Extract the puzzle description of a page as markdown.
+/// This keeps the `<article>` elements and the "Your puzzle answer was" paragraphs that follow solved parts.
+pub fn puzzle_to_markdown(page: &str) -> String {
+    let mut sections: Vec<&str> = vec![];
+    let mut rest = page;
+
+    loop {
+        let article = rest.find("<article");
+        let answer = rest.find("<p>Your puzzle answer was");
+
+        let (start, end_tag) = match (article, answer) {
+            (Some(a), Some(b)) if b < a => (b, "</p>"),
+            (Some(a), _) => (a, "</article>"),
+            (None, Some(b)) => (b, "</p>"),
+            (None, None) => break,
+        };
+
+        let Some(end) = rest[start..].find(end_tag) else {
+            break;
+        };
+
+        let end = start + end + end_tag.len();
+        sections.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+
+    to_markdown(&sections.join("\n"))
+}
+
+/// Convert a HTML fragment to markdown.
+pub fn to_markdown(html: &str) -> String {
+    let tokens = tokenize(html);
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut links: Vec<Option<String>> = vec![];
+
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            Token::Text(text) => {
+                if in_pre {
+                    out.push_str(text);
+                } else {
+                    push_collapsed(&mut out, text);
+                }
+            }
+            Token::Open { name, href } => match name.as_str() {
+                "h2" => {
+                    ensure_blank_line(&mut out);
+                    out.push_str("## ");
+                }
+                "p" => ensure_blank_line(&mut out),
+                "pre" => {
+                    ensure_blank_line(&mut out);
+                    out.push_str("```\n");
+                    in_pre = true;
+                }
+                "code" if !in_pre => {
+                    // `<code><em>42</em></code>` is how answers are highlighted, keep the emphasis outside of the code span.
+                    if let Some(text) = highlighted_code(&tokens[i..]) {
+                        out.push_str(&format!("*`{text}`*"));
+                        i += 5;
+                        continue;
+                    }
+                    out.push('`');
+                }
+                "em" if !in_pre => out.push('*'),
+                "li" => {
+                    ensure_line(&mut out);
+                    out.push_str("- ");
+                }
+                "ul" => ensure_blank_line(&mut out),
+                "a" if !in_pre => {
+                    out.push('[');
+                    links.push(href.clone());
+                }
+                "br" => out.push('\n'),
+                _ => {}
+            },
+            Token::Close(name) => match name.as_str() {
+                "h2" | "p" | "ul" => ensure_blank_line(&mut out),
+                "pre" => {
+                    ensure_line(&mut out);
+                    out.push_str("```\n\n");
+                    in_pre = false;
+                }
+                "code" if !in_pre => out.push('`'),
+                "em" if !in_pre => out.push('*'),
+                "li" => ensure_line(&mut out),
+                "a" if !in_pre => match links.pop().flatten() {
+                    Some(href) => out.push_str(&format!("]({href})")),
+                    None => out.push(']'),
+                },
+                _ => {}
+            },
+        }
+        i += 1;
+    }
+
+    let mut markdown = out.trim().to_string();
+    markdown.push('\n');
+    markdown
+}
+
+fn highlighted_code(tokens: &[Token]) -> Option<&str> {
+    match tokens {
+        [Token::Open { name: code, .. }, Token::Open { name: em, .. }, Token::Text(text), Token::Close(em_close), Token::Close(code_close), ..]
+            if code == "code" && em == "em" && em_close == "em" && code_close == "code" =>
+        {
+            Some(text)
+        }
+        _ => None,
+    }
+}
+
+fn push_collapsed(out: &mut String, text: &str) {
+    let mut last_was_space = out.ends_with([' ', '\n']) || out.is_empty();
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !last_was_space {
+                out.push(' ');
+            }
+            last_was_space = true;
+        } else {
+            out.push(c);
+            last_was_space = false;
+        }
+    }
+}
+
+fn ensure_line(out: &mut String) {
+    while out.ends_with(' ') {
+        out.pop();
+    }
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+fn ensure_blank_line(out: &mut String) {
+    ensure_line(out);
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(tag) = rest.strip_prefix('<') {
+            let Some(end) = tag.find('>') else {
+                tokens.push(Token::Text(decode_entities(rest)));
+                break;
+            };
+            if let Some(token) = parse_tag(&tag[..end]) {
+                tokens.push(token);
+            }
+            rest = &tag[end + 1..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            tokens.push(Token::Text(decode_entities(&rest[..end])));
+            rest = &rest[end..];
+        }
+    }
+
+    tokens
+}
+
+fn parse_tag(tag: &str) -> Option<Token> {
+    if tag.starts_with('!') {
+        return None;
+    }
+
+    if let Some(name) = tag.strip_prefix('/') {
+        return Some(Token::Close(name.trim().to_lowercase()));
+    }
+
+    let tag = tag.trim_end_matches('/');
+    let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    let name = tag[..name_end].to_lowercase();
+
+    let href = tag.find("href=\"").and_then(|start| {
+        let value = &tag[start + 6..];
+        value.find('"').map(|end| decode_entities(&value[..end]))
+    });
+
+    Some(Token::Open { name, href })
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{puzzle_to_markdown, to_markdown};
+
+    #[test]
+    fn converts_paragraphs_and_emphasis() {
+        let html = "<h2>--- Day 1: Test ---</h2><p>The <em>Chief</em>\nis   here &amp; <a href=\"/2024\">there</a>.</p>";
+        assert_eq!(
+            to_markdown(html),
+            "## --- Day 1: Test ---\n\nThe *Chief* is here & [there](/2024).\n"
+        );
+    }
+
+    #[test]
+    fn converts_code_blocks() {
+        let html = "<p>For example:</p>\n<pre><code>3   4\n4   &lt;3\n</code></pre>\n<p>Done.</p>";
+        assert_eq!(
+            to_markdown(html),
+            "For example:\n\n```\n3   4\n4   <3\n```\n\nDone.\n"
+        );
+    }
+
+    #[test]
+    fn converts_highlighted_answers_and_lists() {
+        let html = "<ul>\n<li>One <code>a</code>.</li>\n<li>Two.</li>\n</ul><p>Total: <code><em>11</em></code>.</p>";
+        assert_eq!(to_markdown(html), "- One `a`.\n- Two.\n\nTotal: *`11`*.\n");
+    }
+
+    #[test]
+    fn extracts_articles_and_answers() {
+        let page = "<html><nav>menu</nav><main><article class=\"day-desc\"><h2>--- Day 1 ---</h2><p>One.</p></article>\n<p>Your puzzle answer was <code>42</code>.</p><form>submit</form><article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Two.</p></article></main></html>";
+        assert_eq!(
+            puzzle_to_markdown(page),
+            "## --- Day 1 ---\n\nOne.\n\nYour puzzle answer was `42`.\n\n## --- Part Two ---\n\nTwo.\n"
+        );
+    }
+}
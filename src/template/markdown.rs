@@ -0,0 +1,192 @@
+/// Terminal rendering of the puzzle markdown written by `html::puzzle_to_markdown`.
+/// Headings and highlighted text are printed in bold, code in color, links as their text.
+/// Paragraphs and list items are wrapped, code blocks are indented and kept as-is.
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_CODE: &str = "\x1b[36m";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Style {
+    emphasis: bool,
+    code: bool,
+}
+
+impl Style {
+    fn escape_codes(self) -> String {
+        let mut codes = String::new();
+        if self.emphasis {
+            codes.push_str(ANSI_BOLD);
+        }
+        if self.code {
+            codes.push_str(ANSI_CODE);
+        }
+        codes
+    }
+}
+
+type Word = Vec<(char, Style)>;
+
+/// Render markdown for a terminal that is `width` columns wide.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        if line == "```" {
+            in_code_block = !in_code_block;
+        } else if in_code_block {
+            out.push_str(&format!("    {ANSI_CODE}{line}{ANSI_RESET}\n"));
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            out.push_str(&format!("{ANSI_BOLD}{heading}{ANSI_RESET}\n"));
+        } else if let Some(item) = line.strip_prefix("- ") {
+            out.push_str(&wrap(item, "- ", "  ", width));
+        } else if line.is_empty() {
+            out.push('\n');
+        } else {
+            out.push_str(&wrap(line, "", "", width));
+        }
+    }
+
+    out
+}
+
+/// Wrap a line of inline markdown, starting the first line with `first_prefix` and the others with `prefix`.
+fn wrap(line: &str, first_prefix: &str, prefix: &str, width: usize) -> String {
+    let mut out = first_prefix.to_string();
+    let mut column = first_prefix.chars().count();
+
+    for (i, word) in words(line).into_iter().enumerate() {
+        if i > 0 {
+            if column + 1 + word.len() > width {
+                out.push('\n');
+                out.push_str(prefix);
+                column = prefix.chars().count();
+            } else {
+                out.push(' ');
+                column += 1;
+            }
+        }
+
+        let mut current = Style::default();
+        for (c, style) in &word {
+            if *style != current {
+                if current != Style::default() {
+                    out.push_str(ANSI_RESET);
+                }
+                out.push_str(&style.escape_codes());
+                current = *style;
+            }
+            out.push(*c);
+        }
+        if current != Style::default() {
+            out.push_str(ANSI_RESET);
+        }
+        column += word.len();
+    }
+
+    out.push('\n');
+    out
+}
+
+/// Split a line into styled words, removing the markup.
+fn words(line: &str) -> Vec<Word> {
+    let mut words = vec![];
+    let mut word = Word::new();
+    let mut style = Style::default();
+    // byte offsets of the `]` closing a link's text and of the end of its target.
+    let mut link: Option<(usize, usize)> = None;
+    let mut skip_until = 0;
+
+    for (i, c) in line.char_indices() {
+        if i < skip_until {
+            continue;
+        }
+
+        match c {
+            '`' => style.code = !style.code,
+            _ if style.code => word.push((c, style)),
+            '*' if style.emphasis => style.emphasis = false,
+            '*' if opens_emphasis(&line[i + 1..]) => style.emphasis = true,
+            '[' if link.is_none() => match link_target(&line[i..]) {
+                Some((close, end)) => link = Some((i + close, i + end)),
+                None => word.push((c, style)),
+            },
+            ']' if link.is_some_and(|(close, _)| close == i) => {
+                skip_until = link.take().map_or(0, |(_, end)| end);
+            }
+            ' ' => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            _ => word.push((c, style)),
+        }
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+/// Whether a `*` followed by `rest` starts emphasis: a literal `*`, e.g. in `a * b`, is followed by a space or has no
+/// closing partner.
+fn opens_emphasis(rest: &str) -> bool {
+    rest.starts_with(|c: char| !c.is_whitespace()) && rest.contains('*')
+}
+
+/// For text starting with `[`, find the offsets of the `]` and of the end of `(target)` of a link.
+fn link_target(text: &str) -> Option<(usize, usize)> {
+    let close = text.find("](")?;
+    let end = close + text[close..].find(')')? + 1;
+    Some((close, end))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::render;
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    #[test]
+    fn wraps_paragraphs() {
+        assert_eq!(
+            render("one two three four\n\n- five six seven\n", 10),
+            "one two\nthree four\n\n- five six\n  seven\n"
+        );
+    }
+
+    #[test]
+    fn styles_inline_markup() {
+        assert_eq!(
+            render("The *Chief* is [away](/2024).", 80),
+            format!("The {ANSI_BOLD}Chief{ANSI_RESET} is away.\n")
+        );
+        assert_eq!(
+            render("Total *`11`*.", 80),
+            format!("Total {ANSI_BOLD}\x1b[36m11{ANSI_RESET}.\n")
+        );
+    }
+
+    #[test]
+    fn keeps_literal_asterisks() {
+        assert_eq!(
+            render("Multiply a * b, then *add* c.", 80),
+            format!(
+                "Multiply a * b, then {ANSI_BOLD}add{ANSI_RESET} c.
+"
+            )
+        );
+        assert_eq!(render("Compute 2*3.", 80), "Compute 2*3.\n");
+    }
+
+    #[test]
+    fn keeps_code_blocks() {
+        assert_eq!(
+            render("## --- Day 1 ---\n\n```\n3   4 *\n```\n", 5),
+            format!("{ANSI_BOLD}--- Day 1 ---{ANSI_RESET}\n\n    \x1b[36m3   4 *{ANSI_RESET}\n")
+        );
+    }
+}
@@ -0,0 +1,218 @@
+/// Module that profiles the heap usage of a day with dhat.
+/// The day is run in a `dhat-heap` build, which profiles the parse step and every part separately and prints a
+/// summary for each of them to stderr. The summaries are combined into the peak and total usage of the day.
+use std::{
+    collections::HashMap,
+    process::{Command, Stdio},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+/// Heap usage of a day.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Largest heap size of any part at its peak.
+    pub peak_bytes: u64,
+    pub peak_blocks: u64,
+    /// Sum of all allocations of all parts.
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+}
+
+/// Run a day under dhat and collect its heap usage.
+pub fn profile(day: Day) -> Result<MemoryStats, String> {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--bin",
+            &day.to_string(),
+            "--profile",
+            "dhat",
+            "--features",
+            "dhat-heap",
+            "--",
+            "--format",
+            "quiet",
+        ])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| format!("could not run day {day}: {e}"))?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if !output.status.success() {
+        let reason = stderr.lines().rev().find(|line| !line.trim().is_empty());
+        return Err(format!(
+            "day {day} failed: {}",
+            reason.unwrap_or("no output")
+        ));
+    }
+
+    parse_summaries(&stderr).ok_or(format!("found no dhat summary for day {day}."))
+}
+
+/// Combine the summaries dhat prints when a profiler is dropped, e.g.:
+///
+/// ```text
+/// dhat: Total:     1,256 bytes in 4 blocks
+/// dhat: At t-gmax: 1,024 bytes in 1 blocks
+/// dhat: At t-end:  0 bytes in 0 blocks
+/// ```
+pub fn parse_summaries(output: &str) -> Option<MemoryStats> {
+    let mut stats: Option<MemoryStats> = None;
+
+    for line in output.lines() {
+        let Some(line) = line.trim().strip_prefix("dhat:") else {
+            continue;
+        };
+
+        if let Some(values) = line.trim().strip_prefix("Total:") {
+            let (bytes, blocks) = parse_bytes_in_blocks(values)?;
+            let stats = stats.get_or_insert_with(MemoryStats::default);
+            stats.total_bytes += bytes;
+            stats.total_blocks += blocks;
+        } else if let Some(values) = line.trim().strip_prefix("At t-gmax:") {
+            let (bytes, blocks) = parse_bytes_in_blocks(values)?;
+            let stats = stats.get_or_insert_with(MemoryStats::default);
+            if bytes > stats.peak_bytes {
+                stats.peak_bytes = bytes;
+                stats.peak_blocks = blocks;
+            }
+        }
+    }
+
+    stats
+}
+
+/// Parse `1,256 bytes in 4 blocks`.
+fn parse_bytes_in_blocks(s: &str) -> Option<(u64, u64)> {
+    let number = |s: &str| s.trim().replace(',', "").parse::<u64>().ok();
+    let (bytes, blocks) = s.split_once(" bytes in ")?;
+    Some((
+        number(bytes)?,
+        number(blocks.trim().strip_suffix("blocks")?)?,
+    ))
+}
+
+/// Format a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MemoryStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let numbers = [
+            ("peak_bytes", value.peak_bytes),
+            ("peak_blocks", value.peak_blocks),
+            ("total_bytes", value.total_bytes),
+            ("total_blocks", value.total_blocks),
+        ];
+
+        for (key, number) in numbers {
+            map.insert(key.into(), JsonValue::Number(number as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|x| x as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            peak_bytes: number("peak_bytes")?,
+            peak_blocks: number("peak_blocks")?,
+            total_bytes: number("total_bytes")?,
+            total_blocks: number("total_blocks")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_bytes, parse_summaries, MemoryStats};
+
+    #[test]
+    fn parses_summaries() {
+        let output = [
+            "dhat: Total:     1,256 bytes in 4 blocks",
+            "dhat: At t-gmax: 1,024 bytes in 1 blocks",
+            "dhat: At t-end:  0 bytes in 0 blocks",
+            "dhat: The data has been saved to dhat-heap.json, and is viewable with dhat/dh_view.html",
+            "dhat: Total:     40,000 bytes in 100 blocks",
+            "dhat: At t-gmax: 2,048 bytes in 12 blocks",
+            "dhat: At t-end:  0 bytes in 0 blocks",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            parse_summaries(&output),
+            Some(MemoryStats {
+                peak_bytes: 2048,
+                peak_blocks: 12,
+                total_bytes: 41_256,
+                total_blocks: 104,
+            })
+        );
+        assert_eq!(parse_summaries("Part 1: 42 (1.0ms)"), None);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn roundtrips_memory_stats() {
+        let stats = MemoryStats {
+            peak_bytes: 2048,
+            peak_blocks: 12,
+            total_bytes: 41_256,
+            total_blocks: 104,
+        };
+        let json = JsonValue::from(&stats);
+        assert_eq!(MemoryStats::try_from(&json), Ok(stats));
+    }
+}
@@ -1,14 +1,33 @@
-use std::{env, fs};
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
 
-pub mod aoc_cli;
+pub mod alloc;
+pub mod aoc_client;
 pub mod commands;
+pub mod compare;
+pub mod examples;
+pub mod export;
+pub mod progress;
+pub mod registry;
 pub mod runner;
+pub mod unlocks;
 
 pub use day::*;
 
+mod answers;
+mod crypt;
 mod day;
+#[cfg(all(test, feature = "test_lib"))]
+mod fixtures;
+mod history;
+mod html;
+mod markdown;
+mod memory;
+mod puzzle;
 mod readme_benchmarks;
+mod readme_stars;
 mod run_multi;
+mod submissions;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -18,10 +37,52 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
+    try_read_file(folder, day).unwrap_or_else(|e| panic!("could not open input file: {e}"))
+}
+
+/// Helper function that reads a text file to a string, returning an error if it can not be read.
+/// If the file does not exist but an encrypted copy of it does, e.g. `01.txt.enc`, the copy is decrypted.
+pub fn try_read_file(folder: &str, day: Day) -> io::Result<String> {
+    let cwd = env::current_dir()?;
     let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_or_decrypt(&filepath, crypt::Key::from_env)
+}
+
+/// Path of the encrypted copy of a file, e.g. `01.txt.enc` for `01.txt`.
+fn encrypted_path(filepath: &Path) -> PathBuf {
+    let mut name = filepath.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", crypt::EXTENSION));
+    filepath.with_file_name(name)
+}
+
+/// Whether an encrypted copy of the input of `day` exists.
+#[must_use]
+pub fn has_encrypted_input(day: Day) -> bool {
+    encrypted_path(Path::new(&format!("data/inputs/{day}.txt"))).exists()
+}
+
+/// Read a file, falling back to its encrypted copy if the file is missing or empty, e.g. a placeholder created by an
+/// older version of `scaffold`. The key is only loaded if the copy is needed.
+fn read_or_decrypt(
+    filepath: &Path,
+    key: impl FnOnce() -> Result<crypt::Key, crypt::CryptError>,
+) -> io::Result<String> {
+    let result = fs::read_to_string(filepath);
+    let is_missing = match &result {
+        Ok(plain) => plain.is_empty(),
+        Err(e) => e.kind() == io::ErrorKind::NotFound,
+    };
+    if !is_missing {
+        return result;
+    }
+
+    let Ok(encrypted) = fs::read(encrypted_path(filepath)) else {
+        return result;
+    };
+    let name = filepath.file_name().unwrap_or_default().to_string_lossy();
+    let key = key().map_err(io::Error::other)?;
+    let plain = crypt::decrypt(&key, &name, &encrypted).map_err(io::Error::other)?;
+    String::from_utf8(plain).map_err(io::Error::other)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -39,6 +100,19 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// The input is read from `data/inputs`, unless the binary is called with `--input <path>` or `--input -` for stdin.
+///
+/// The optional `parse = <fn>` parameter sets up a parse step that is shared by both parts. It is called once with
+/// the input and its output is passed to the parts by reference. Its duration is reported separately.
+///
+/// With the `count-allocs` feature, a counting global allocator is installed and the allocations of every part are
+/// reported next to its duration.
+///
+/// The parts are also exposed as the constant `SOLUTION`, which the main binary uses to run the day in-process.
+/// A panicking part does not stop the other parts, but the binary still exits with an error.
+///
+/// For every example of the day with expected answers in `data/examples`, a test is generated (see `examples`).
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,8 +124,43 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1]);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@define $day, |input, options| {
+            use $crate::template::runner::{run_isolated, run_part};
+            let mut results = vec![];
+            $(
+                results.extend(run_isolated(|| run_part($func, input, DAY, $part, options)).ok());
+            )*
+            results
+        });
+    };
+
+    (@impl $day:expr, parse = $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@define $day, |input, options| {
+            use $crate::template::runner::{parse_failed, run_isolated, run_parse, run_part};
+            let (parsed, parse_result) = match run_isolated(|| run_parse($parse, input, DAY, options)) {
+                Ok(x) => x,
+                Err(message) => return vec![parse_failed(DAY, &message, options)],
+            };
+            let mut results = vec![parse_result];
+            $(
+                results.extend(run_isolated(|| run_part($func, &parsed, DAY, $part, options)).ok());
+            )*
+            results
+        });
+    };
+
+    (@define $day:expr, $run:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +168,71 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(feature = "count-allocs")]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
+
+        /// The solution of the current day, as registered with the main binary.
+        pub const SOLUTION: $crate::template::registry::Solution = $crate::template::registry::Solution {
+            day: DAY,
+            run: $run,
+        };
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = read_input(DAY);
+            SOLUTION.run(&input, &RunOptions::from_args());
+            if panic_count() > 0 {
+                std::process::exit(101);
+            }
+        }
+
+        /// Tests for the examples with expected answers, generated by `build.rs`.
+        #[cfg(test)]
+        mod example_tests {
+            include!(concat!(env!("OUT_DIR"), "/examples_", $day, ".rs"));
         }
     };
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::{env, fs, io, process};
+
+    use super::{crypt, read_or_decrypt};
+
+    #[test]
+    fn decrypts_missing_inputs() {
+        let dir = env::temp_dir().join(format!("aoc-read-file-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let key = || "a".repeat(64).parse::<crypt::Key>();
+
+        let path = dir.join("01.txt");
+        let encrypted = crypt::encrypt(&key().unwrap(), "01.txt", b"3   4\n").unwrap();
+        fs::write(dir.join("01.txt.enc"), encrypted).unwrap();
+
+        assert_eq!(read_or_decrypt(&path, key).unwrap(), "3   4\n");
+
+        // an empty file does not shadow its encrypted copy.
+        fs::write(&path, "").unwrap();
+        assert_eq!(read_or_decrypt(&path, key).unwrap(), "3   4\n");
+
+        // a plain file takes precedence over its encrypted copy.
+        fs::write(&path, "4   3\n").unwrap();
+        assert_eq!(read_or_decrypt(&path, key).unwrap(), "4   3\n");
+
+        let missing = read_or_decrypt(&dir.join("02.txt"), key).unwrap_err();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+
+        fs::write(dir.join("03.txt"), "").unwrap();
+        assert_eq!(read_or_decrypt(&dir.join("03.txt"), key).unwrap(), "");
+
+        let wrong_key = || "b".repeat(64).parse::<crypt::Key>();
+        fs::remove_file(&path).unwrap();
+        assert!(read_or_decrypt(&path, wrong_key).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
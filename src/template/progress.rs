@@ -0,0 +1,105 @@
+/// Progress reporting for long-running parts.
+/// A solution calls `report` with the number of finished and total steps, and the runner renders it as a live line
+/// while the part runs. Reports are ignored when the part is timed, when the output is not a terminal and in tests,
+/// so calling `report` in a hot loop only costs an atomic load.
+use std::io::{stdout, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Minimum time between two redraws of the progress line.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+const BAR_WIDTH: usize = 20;
+
+const ANSI_CLEAR_LINE: &str = "\r\x1b[2K";
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+static STATE: Mutex<State> = Mutex::new(State {
+    label: String::new(),
+    last_draw: None,
+});
+
+struct State {
+    label: String,
+    last_draw: Option<Instant>,
+}
+
+/// Report that `done` of `total` steps of the current part are finished.
+pub fn report(done: usize, total: usize) {
+    if !ACTIVE.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let Ok(mut state) = STATE.lock() else {
+        return;
+    };
+
+    let now = Instant::now();
+    if state
+        .last_draw
+        .is_some_and(|last| now.duration_since(last) < REDRAW_INTERVAL)
+        && done < total
+    {
+        return;
+    }
+
+    print!(
+        "{ANSI_CLEAR_LINE}{}: {}",
+        state.label,
+        format_progress(done, total)
+    );
+    let _ = stdout().flush();
+    state.last_draw = Some(now);
+}
+
+/// Render reports of the part labeled `label` until `finish` is called.
+pub fn start(label: &str) {
+    if let Ok(mut state) = STATE.lock() {
+        state.label = label.to_string();
+        state.last_draw = None;
+    }
+    ACTIVE.store(true, Ordering::Relaxed);
+}
+
+/// Stop rendering reports and clear the progress line, if one was drawn.
+pub fn finish() {
+    ACTIVE.store(false, Ordering::Relaxed);
+    if let Ok(mut state) = STATE.lock() {
+        if state.last_draw.take().is_some() {
+            print!("{ANSI_CLEAR_LINE}");
+            let _ = stdout().flush();
+        }
+    }
+}
+
+/// Format progress as a bar followed by the counts, e.g. `[#####---------------] 25/100 (25%)`.
+fn format_progress(done: usize, total: usize) -> String {
+    let done = done.min(total);
+    let filled = (done * BAR_WIDTH).checked_div(total).unwrap_or(BAR_WIDTH);
+    let percent = (done * 100).checked_div(total).unwrap_or(100);
+
+    format!(
+        "[{}{}] {done}/{total} ({percent}%)",
+        "#".repeat(filled),
+        "-".repeat(BAR_WIDTH - filled)
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::format_progress;
+
+    #[test]
+    fn formats_progress() {
+        assert_eq!(
+            format_progress(25, 100),
+            "[#####---------------] 25/100 (25%)"
+        );
+        assert_eq!(format_progress(3, 3), "[####################] 3/3 (100%)");
+        assert_eq!(format_progress(0, 0), "[####################] 0/0 (100%)");
+    }
+}
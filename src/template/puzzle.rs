@@ -0,0 +1,174 @@
+/// Extraction of examples from the puzzle descriptions in `data/puzzles`.
+/// Descriptions are the markdown written by `html::puzzle_to_markdown`: examples are fenced code blocks and
+/// highlighted answers are written as ``*`42`*``.
+use std::mem;
+
+use crate::template::examples::ExpectedAnswers;
+
+const PART_TWO_HEADING: &str = "## --- Part Two ---";
+
+/// A code block of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// The part whose description contains the block.
+    pub part: u8,
+    pub text: String,
+    /// The last highlighted value between this block and the next one, which is usually the example's answer.
+    answer: Option<String>,
+}
+
+/// The examples of a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    pub examples: Vec<Example>,
+    /// The answer of part two if its description has no code block, i.e. reuses the example of part one.
+    shared_answer: Option<String>,
+}
+
+impl Puzzle {
+    pub fn parse(markdown: &str) -> Self {
+        let mut puzzle = Puzzle::default();
+        let mut part = 1;
+        let mut prose = String::new();
+        let mut block: Option<String> = None;
+
+        for line in markdown.lines() {
+            if let Some(text) = block.as_mut() {
+                if line == "```" {
+                    puzzle.examples.push(Example {
+                        part,
+                        text: mem::take(text),
+                        answer: None,
+                    });
+                    block = None;
+                } else {
+                    text.push_str(line);
+                    text.push('\n');
+                }
+            } else if line == "```" {
+                puzzle.close_section(part, &mem::take(&mut prose));
+                block = Some(String::new());
+            } else if line.starts_with(PART_TWO_HEADING) {
+                puzzle.close_section(part, &mem::take(&mut prose));
+                part = 2;
+            } else {
+                prose.push_str(line);
+                prose.push('\n');
+            }
+        }
+
+        puzzle.close_section(part, &prose);
+        puzzle
+    }
+
+    /// Get the expected answers of an example.
+    /// The first example also gets the answer of part two if part two has no example of its own.
+    pub fn expected_answers(&self, index: usize) -> Option<ExpectedAnswers> {
+        let example = self.examples.get(index)?;
+        let mut answers = ExpectedAnswers::default();
+
+        match example.part {
+            1 => answers.part_one.clone_from(&example.answer),
+            _ => answers.part_two.clone_from(&example.answer),
+        }
+
+        if index == 0 && self.examples.iter().all(|e| e.part == 1) {
+            answers.part_two.clone_from(&self.shared_answer);
+        }
+
+        Some(answers)
+    }
+
+    /// Attribute the prose that ends at a code block or at the end of a part.
+    fn close_section(&mut self, part: u8, prose: &str) {
+        let answer = last_highlighted(prose);
+        match self.examples.last_mut() {
+            Some(example) if example.part == part => example.answer = answer,
+            _ if part == 2 => self.shared_answer = answer,
+            _ => {}
+        }
+    }
+}
+
+/// Get the title of a puzzle from the heading of its description, e.g. `Historian Hysteria`.
+pub fn title(markdown: &str) -> Option<&str> {
+    let heading = markdown.lines().next()?.strip_prefix("## --- ")?;
+    let (_, title) = heading.strip_suffix(" ---")?.split_once(": ")?;
+    Some(title)
+}
+
+/// Get the description of a single part, or `None` if the part is not unlocked.
+pub fn part(markdown: &str, part: u8) -> Option<&str> {
+    let index = markdown.find(PART_TWO_HEADING);
+    match (part, index) {
+        (1, Some(index)) => Some(&markdown[..index]),
+        (1, None) => Some(markdown),
+        (2, Some(index)) => Some(&markdown[index..]),
+        _ => None,
+    }
+}
+
+fn last_highlighted(markdown: &str) -> Option<String> {
+    let end = markdown.rfind("`*")?;
+    let start = markdown[..end].rfind("*`")?;
+    let value = &markdown[start + 2..end];
+    (!value.is_empty() && !value.contains('`')).then(|| value.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{part, title, Puzzle};
+
+    #[test]
+    fn finds_examples_and_answers() {
+        let markdown = "## --- Day 1 ---\n\nThe answer is not *`0`*. For example:\n\n```\n3   4\n4   3\n```\n\nFirst *`2`*, in total *`11`*. Another example:\n\n```\n1   2\n```\n\nThis gives *`1`*.\n\nYour puzzle answer was `42`.\n\n## --- Part Two ---\n\nFor the first example, the score is *`31`*.\n";
+        let puzzle = Puzzle::parse(markdown);
+
+        assert_eq!(puzzle.examples.len(), 2);
+        assert_eq!(puzzle.examples[0].text, "3   4\n4   3\n");
+
+        let answers = puzzle.expected_answers(0).unwrap();
+        assert_eq!(answers.get(1), Some("11"));
+        assert_eq!(answers.get(2), Some("31"));
+
+        let answers = puzzle.expected_answers(1).unwrap();
+        assert_eq!(answers.get(1), Some("1"));
+        assert_eq!(answers.get(2), None);
+
+        assert_eq!(puzzle.expected_answers(2), None);
+    }
+
+    #[test]
+    fn handles_separate_examples_per_part() {
+        let markdown = "## --- Day 1 ---\n\n```\na\n```\n\nResult: *`7`*.\n\n## --- Part Two ---\n\nNow *`twice`*:\n\n```\nb\n```\n\nResult: *`9`*.\n";
+        let puzzle = Puzzle::parse(markdown);
+        assert_eq!(puzzle.examples[1].part, 2);
+
+        let answers = puzzle.expected_answers(0).unwrap();
+        assert_eq!(answers.get(1), Some("7"));
+        assert_eq!(answers.get(2), None);
+
+        let answers = puzzle.expected_answers(1).unwrap();
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.get(2), Some("9"));
+    }
+
+    #[test]
+    fn reads_titles() {
+        assert_eq!(
+            title("## --- Day 1: Historian Hysteria ---\n\nText."),
+            Some("Historian Hysteria")
+        );
+        assert_eq!(title("Text."), None);
+    }
+
+    #[test]
+    fn splits_parts() {
+        let markdown = "## --- Day 1: A ---\n\nOne.\n\n## --- Part Two ---\n\nTwo.\n";
+        assert_eq!(part(markdown, 1), Some("## --- Day 1: A ---\n\nOne.\n\n"));
+        assert_eq!(part(markdown, 2), Some("## --- Part Two ---\n\nTwo.\n"));
+        assert_eq!(part("## --- Day 1: A ---\n", 2), None);
+    }
+}
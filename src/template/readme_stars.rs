@@ -0,0 +1,214 @@
+/// Module that updates the readme with the stars collected so far.
+/// The table has the format of the `advent-readme-stars` workflow, but is built from the local record of solved
+/// parts: accepted answers in `data/answers.json` and correct submissions in `data/submissions.json`.
+use std::{collections::BTreeMap, env, fs};
+
+use crate::template::answers::Answers;
+use crate::template::history::civil_from_days;
+use crate::template::readme_benchmarks::{replace_table, Error};
+use crate::template::submissions::{Submissions, Verdict};
+use crate::template::Day;
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// A solved part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Star {
+    /// Seconds since the unix epoch, if the part was solved with `--submit`.
+    pub solved_at: Option<u64>,
+}
+
+/// Solved parts by day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stars {
+    pub data: BTreeMap<Day, [Option<Star>; 2]>,
+}
+
+impl Stars {
+    /// Collect the solved parts from the answers and submissions logs.
+    pub fn collect(answers: &Answers, submissions: &Submissions) -> Self {
+        let mut stars = Stars::default();
+
+        for answer in &answers.data {
+            for (part, value) in [(1, &answer.part_1), (2, &answer.part_2)] {
+                if value.is_some() {
+                    stars.add(answer.day, part, None);
+                }
+            }
+        }
+
+        for attempt in &submissions.data {
+            match attempt.verdict {
+                Verdict::Correct => stars.add(attempt.day, attempt.part, Some(attempt.timestamp)),
+                Verdict::AlreadyCompleted => stars.add(attempt.day, attempt.part, None),
+                _ => {}
+            }
+        }
+
+        stars
+    }
+
+    /// Record a solved part, keeping the earliest known time it was solved at.
+    fn add(&mut self, day: Day, part: u8, solved_at: Option<u64>) {
+        let parts = self.data.entry(day).or_default();
+        let Some(slot) = usize::from(part)
+            .checked_sub(1)
+            .and_then(|index| parts.get_mut(index))
+        else {
+            return;
+        };
+
+        let star = slot.get_or_insert(Star { solved_at: None });
+        star.solved_at = match (star.solved_at, solved_at) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+    }
+
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.data.values().flatten().flatten().count()
+    }
+}
+
+/// Format a unix timestamp as `YYYY-MM-DD` in UTC.
+fn format_date(timestamp: u64) -> String {
+    let (year, month, day) = civil_from_days(timestamp / 86_400);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn format_star(star: Option<Star>) -> String {
+    match star {
+        Some(Star {
+            solved_at: Some(timestamp),
+        }) => format!("⭐ {}", format_date(timestamp)),
+        Some(Star { solved_at: None }) => "⭐".into(),
+        None => " ".into(),
+    }
+}
+
+fn construct_table(prefix: &str, stars: &Stars, year: Option<&str>) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Results"),
+        None => format!("{prefix} Results"),
+    };
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for (day, [part_1, part_2]) in &stars.data {
+        let day = day.into_inner();
+        let link = match year {
+            Some(year) => format!("[Day {day}](https://adventofcode.com/{year}/day/{day})"),
+            None => format!("Day {day}"),
+        };
+        lines.push(format!(
+            "| {link} | {} | {} |",
+            format_star(*part_1),
+            format_star(*part_2)
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {} ⭐**", stars.count()));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, stars: &Stars, year: Option<&str>) -> Result<(), Error> {
+    let table = construct_table("##", stars, year);
+    replace_table(s, MARKER, &table)
+}
+
+pub fn update(stars: &Stars) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let year = env::var("AOC_YEAR").ok();
+    update_content(&mut readme, stars, year.as_deref())?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{update_content, Stars, MARKER};
+    use crate::day;
+    use crate::template::answers::Answers;
+    use crate::template::aoc_client::SubmissionOutcome;
+    use crate::template::submissions::Submissions;
+
+    fn get_mock_stars() -> Stars {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "11");
+        answers.set(day!(1), 2, "31");
+        answers.set(day!(3), 1, "161");
+
+        let mut submissions = Submissions::default();
+        // 2024-12-01 05:10 and 2024-12-02 06:00 UTC.
+        submissions.record(day!(1), 1, "11", &SubmissionOutcome::Correct, 1_733_029_800);
+        submissions.record(day!(1), 2, "31", &SubmissionOutcome::Correct, 1_733_119_200);
+        submissions.record(day!(2), 2, "4", &SubmissionOutcome::Correct, 1_733_119_200);
+        submissions.record(
+            day!(2),
+            1,
+            "2",
+            &SubmissionOutcome::AlreadyCompleted,
+            1_733_119_300,
+        );
+
+        Stars::collect(&answers, &submissions)
+    }
+
+    #[test]
+    fn collects_stars() {
+        let stars = get_mock_stars();
+        assert_eq!(stars.count(), 5);
+        assert_eq!(stars.data.len(), 3);
+        assert_eq!(stars.data[&day!(3)][1], None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_stars(), Some("2024")).unwrap();
+    }
+
+    #[test]
+    fn updates_existing_stars() {
+        let mut s = format!("foo\n{}\nbar", MARKER);
+        update_content(&mut s, &get_mock_stars(), Some("2024")).unwrap();
+        update_content(&mut s, &get_mock_stars(), Some("2024")).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## 2024 Results").count(), 1);
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{}\nbar", MARKER);
+        update_content(&mut s, &get_mock_stars(), Some("2024")).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ 2024-12-01 | ⭐ 2024-12-02 |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ | ⭐ 2024-12-02 |",
+            "| [Day 3](https://adventofcode.com/2024/day/3) | ⭐ |   |",
+            "",
+            "**Total: 5 ⭐**",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
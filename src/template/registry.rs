@@ -0,0 +1,30 @@
+/// In-process registry of solutions.
+/// Every scaffolded day exposes a `SOLUTION` constant via the `solution!` macro. The main binary collects these
+/// at build time so `all` and `time` can call each day's parts directly instead of spawning `cargo run` per day.
+use crate::template::runner::{PartResult, RunOptions};
+use crate::template::Day;
+
+/// Signature of a type-erased solution. Runs the parse step, if any, and every part of a day.
+pub type SolutionFn = fn(&str, &RunOptions) -> Vec<PartResult>;
+
+/// A day's solution.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: Day,
+    pub run: SolutionFn,
+}
+
+impl Solution {
+    /// Run this solution against `input`. Results are returned in order, starting with the parse step if present.
+    /// A part that panics is left out of the results, so the other parts still run. If the parse step panics, no
+    /// parts run and the only result is the parse step with the panic as its error.
+    pub fn run(&self, input: &str, options: &RunOptions) -> Vec<PartResult> {
+        (self.run)(input, options)
+    }
+}
+
+/// Look up the solution for a given day.
+#[must_use]
+pub fn find(solutions: &[Solution], day: Day) -> Option<&Solution> {
+    solutions.iter().find(|solution| solution.day == day)
+}
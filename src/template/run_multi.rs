@@ -1,257 +1,542 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    env,
+    fmt::Display,
+    io::{self, BufRead, BufReader, Read, Write},
+    process::{Command, ExitStatus, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::registry::{self, Solution};
+use crate::template::runner::{format_part_result, OutputFormat, PartResult, Schedule, PARSE_STEP};
+use crate::template::{try_read_file, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// How a day ended when run in a child process.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DayStatus {
+    /// All parts returned an answer.
+    Ok,
+    /// At least one part returned `None`.
+    Unsolved,
+    /// A part returned an error, contains the part and the error message.
+    Errored(u8, String),
+    /// The solution panicked, contains the panic message.
+    Panicked(String),
+    /// The solution did not finish in time and was killed.
+    TimedOut(Duration),
+    /// The child process could not be started or exited unexpectedly.
+    Failed(String),
+    /// The day has a solution but no input file.
+    MissingInput,
+}
 
-    let mut need_space = false;
+impl DayStatus {
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            DayStatus::Errored(..)
+                | DayStatus::Panicked(_)
+                | DayStatus::TimedOut(_)
+                | DayStatus::Failed(_)
+        )
+    }
+}
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+impl Display for DayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayStatus::Ok => write!(f, "ok"),
+            DayStatus::Unsolved => write!(f, "unsolved"),
+            DayStatus::Errored(part, message) => write!(f, "part {part} failed: {message}"),
+            DayStatus::Panicked(message) => write!(f, "panicked: {message}"),
+            DayStatus::TimedOut(timeout) => write!(f, "timed out after {}s", timeout.as_secs()),
+            DayStatus::Failed(reason) => write!(f, "failed: {reason}"),
+            DayStatus::MissingInput => write!(f, "no input"),
+        }
+    }
+}
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+/// Outcome of running a single day.
+#[derive(Clone, Debug)]
+pub struct DayReport {
+    pub day: Day,
+    pub results: Vec<PartResult>,
+    pub status: DayStatus,
+}
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+impl DayReport {
+    /// Summarize a part for the status table: ✔ if it returned an answer, ✖ if it did not, ⚠ if it returned an
+    /// error, - if it did not finish.
+    fn part_cell(&self, part: u8) -> &'static str {
+        match self.results.iter().find(|r| r.part == part) {
+            Some(result) if result.answer.is_some() => "✔",
+            Some(result) if result.error.is_some() => "⚠",
+            Some(_) => "✖",
+            None => "-",
+        }
+    }
+}
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+/// Outcome of running a set of days.
+pub struct MultiRun {
+    /// Timings of all days that were run, if timed.
+    pub timings: Option<Timings>,
+    pub reports: Vec<DayReport>,
+}
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
+impl MultiRun {
+    pub fn has_failures(&self) -> bool {
+        self.reports.iter().any(|r| r.status.is_failure())
     }
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
+/// Destination of the output of a day.
+/// Output is printed right away when days run sequentially, and buffered until the day is done when they run in
+/// parallel, so that days are printed in order.
+#[derive(Default)]
+struct DayOutput {
+    is_buffered: bool,
+    stdout: String,
+    stderr: String,
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+impl DayOutput {
+    fn print(&mut self, s: &str) {
+        if self.is_buffered {
+            self.stdout.push_str(s);
+        } else {
+            print!("{s}");
+            let _ = io::stdout().flush();
+        }
+    }
+
+    fn eprint(&mut self, s: &str) {
+        if self.is_buffered {
+            self.stderr.push_str(s);
+        } else {
+            eprint!("{s}");
+        }
     }
-}
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    fn flush(self) {
+        print!("{}", self.stdout);
+        eprint!("{}", self.stderr);
+    }
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+/// Run a set of days. Every day runs in a child process of the main binary (see `commands::run_day`), so that a
+/// panicking day does not interrupt the run and a day that exceeds the timeout of `schedule` can be killed.
+/// With more than one job, days run concurrently and their output is printed in order once they are done.
+pub fn run_multi(
+    solutions: &[Solution],
+    days_to_run: &HashSet<Day>,
+    is_timed: bool,
+    format: OutputFormat,
+    schedule: Schedule,
+) -> MultiRun {
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+    let is_human = format == OutputFormat::Human;
+
+    let run = |index: usize, output: &mut DayOutput| {
+        let day = days[index];
+        if is_human {
+            if index > 0 {
+                output.print("\n");
+            }
+            output.print(&format!("{ANSI_BOLD}Day {day}{ANSI_RESET}\n------\n"));
+        }
+        run_day(solutions, day, is_timed, format, schedule.timeout, output)
+    };
+
+    let reports: Vec<DayReport> = if schedule.jobs > 1 {
+        run_parallel(days.len(), schedule.jobs, run)
+    } else {
+        (0..days.len())
+            .filter_map(|index| run(index, &mut DayOutput::default()))
+            .collect()
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    if is_human {
+        print_summary(&reports);
+    } else {
+        for report in reports.iter().filter(|r| r.status.is_failure()) {
+            eprintln!("Day {} {}", report.day, report.status);
+        }
+    }
+
+    let timings = is_timed.then(|| {
+        let timings = Timings {
+            data: reports
+                .iter()
+                .filter(|r| r.status != DayStatus::MissingInput)
+                .map(|r| Timing::from_results(r.day, &r.results))
+                .collect(),
+        };
+        if is_human {
+            let total_millis = timings.total_millis();
+            println!(
+                "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
         }
+        timings
+    });
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    MultiRun { timings, reports }
+}
 
-        if is_release {
-            args.push("--release");
+/// Run `count` days on `jobs` worker threads. The buffered output of each day is printed as soon as all days
+/// before it are done.
+fn run_parallel(
+    count: usize,
+    jobs: usize,
+    run: impl Fn(usize, &mut DayOutput) -> Option<DayReport> + Sync,
+) -> Vec<DayReport> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(count) {
+            let sender = sender.clone();
+            let (next, run) = (&next, &run);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                if index >= count {
+                    break;
+                }
+                let mut output = DayOutput {
+                    is_buffered: true,
+                    ..DayOutput::default()
+                };
+                let report = run(index, &mut output);
+                if sender.send((index, output, report)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut reports = vec![];
+        let mut next_to_print = 0;
+
+        for (index, output, report) in receiver {
+            pending.insert(index, (output, report));
+            while let Some((output, report)) = pending.remove(&next_to_print) {
+                output.flush();
+                reports.extend(report);
+                next_to_print += 1;
+            }
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        reports
+    })
+}
+
+/// Run a single day, if it has a solution.
+fn run_day(
+    solutions: &[Solution],
+    day: Day,
+    is_timed: bool,
+    format: OutputFormat,
+    timeout: Option<Duration>,
+    output: &mut DayOutput,
+) -> Option<DayReport> {
+    let is_human = format == OutputFormat::Human;
+
+    if registry::find(solutions, day).is_none() {
+        if is_human {
+            output.print("Not solved.\n");
         }
+        return None;
+    }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+    if let Err(e) = try_read_file("inputs", day) {
+        output.eprint(&format!("Could not read input file for day {day}: {e}\n"));
+        if is_human {
+            output.print("Not solved.\n");
+        }
+        return Some(DayReport {
+            day,
+            results: vec![],
+            status: DayStatus::MissingInput,
+        });
+    }
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    let report = run_child(day, is_timed, format, timeout, output);
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+    if is_human && report.status.is_failure() {
+        output.print(&format!("{ANSI_BOLD}✖ {}{ANSI_RESET}\n", report.status));
+    }
 
-        let mut output = vec![];
+    Some(report)
+}
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+/// Run a day in a child process and collect the records it emits.
+fn run_child(
+    day: Day,
+    is_timed: bool,
+    format: OutputFormat,
+    timeout: Option<Duration>,
+    output: &mut DayOutput,
+) -> DayReport {
+    let failed = |reason: String| DayReport {
+        day,
+        results: vec![],
+        status: DayStatus::Failed(reason),
+    };
+
+    let exe = match env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => return failed(e.to_string()),
+    };
+
+    let mut args = vec!["run-day".to_string(), day.to_string()];
+    if is_timed {
+        args.push("--time".into());
+    }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+    let mut child = match Command::new(exe)
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return failed(e.to_string()),
+    };
+
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
         }
+    });
 
-        thread.join().unwrap();
-        cmd.wait()?;
+    let stderr_reader = thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stderr.read_to_string(&mut output);
+        output
+    });
 
-        Ok(output)
-    }
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let mut results: Vec<PartResult> = vec![];
+    let mut timed_out = false;
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    loop {
+        let line = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+        match line {
+            Ok(line) => match line.parse::<PartResult>() {
+                Ok(result) => {
+                    output.print(&format_part_result(&result, format));
+                    results.push(result);
                 }
+                // output of the solution itself is passed through.
+                Err(_) => {
+                    if format == OutputFormat::Human {
+                        output.print(&format!("{line}\n"));
+                    }
+                }
+            },
+            Err(RecvTimeoutError::Timeout) => {
+                let _ = child.kill();
+                timed_out = true;
+                break;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+    let exit_status = child.wait();
+    let stderr = stderr_reader.join().unwrap_or_default();
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+    let status = match (timed_out, exit_status) {
+        (true, _) => DayStatus::TimedOut(timeout.unwrap_or_default()),
+        (false, Ok(exit_status)) => {
+            if exit_status.success() {
+                output.eprint(&stderr);
+            } else {
+                output.eprint(&strip_panic(&stderr));
+            }
+            day_status(exit_status, &stderr, &results)
+        }
+        (false, Err(e)) => DayStatus::Failed(e.to_string()),
+    };
 
-                timings.total_nanos += nanos;
-            });
+    DayReport {
+        day,
+        results,
+        status,
+    }
+}
 
-        timings
+fn day_status(exit_status: ExitStatus, stderr: &str, results: &[PartResult]) -> DayStatus {
+    if let Some(message) = panic_message(stderr) {
+        DayStatus::Panicked(message)
+    } else if !exit_status.success() {
+        DayStatus::Failed(exit_status.to_string())
+    } else if let Some(result) = results.iter().find(|r| r.error.is_some()) {
+        DayStatus::Errored(result.part, result.error.clone().unwrap_or_default())
+    } else if results
+        .iter()
+        .any(|r| r.part != PARSE_STEP && r.answer.is_none())
+    {
+        DayStatus::Unsolved
+    } else {
+        DayStatus::Ok
     }
+}
+
+/// Extract the message of a panic from the output of the default panic hook, e.g.
+/// "thread 'main' panicked at src/bin/24.rs:10:5:\nnot yet implemented".
+fn panic_message(stderr: &str) -> Option<String> {
+    let mut lines = stderr
+        .lines()
+        .skip_while(|line| !line.contains("panicked at"));
+    let location = lines.next()?;
+
+    // NOTE: rust < 1.73 prints the message on the same line, e.g. "panicked at 'message', src/main.rs:1:1".
+    let message = match location.split_once("panicked at '") {
+        Some((_, rest)) => rest.rsplit_once("', ").map_or(rest, |(message, _)| message),
+        None => lines.next().unwrap_or_default(),
+    };
+
+    Some(message.trim().to_string())
+}
+
+/// Remove the output of the default panic hook, which is summarized in the status table instead.
+fn strip_panic(stderr: &str) -> String {
+    stderr
+        .lines()
+        .take_while(|line| !line.contains("panicked at"))
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+/// Print a table with the status of every day that has a solution.
+fn print_summary(reports: &[DayReport]) {
+    if reports.is_empty() {
+        return;
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    println!();
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("------");
+    for line in summary_table(reports) {
+        println!("{line}");
     }
+}
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+fn summary_table(reports: &[DayReport]) -> Vec<String> {
+    let mut lines = vec![
+        "| Day | Part 1 | Part 2 | Status |".to_string(),
+        "| :---: | :---: | :---: | :--- |".to_string(),
+    ];
+
+    for report in reports {
+        lines.push(format!(
+            "| {} | {} | {} | {} |",
+            report.day,
+            report.part_cell(1),
+            report.part_cell(2),
+            report.status
+        ));
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    lines
+}
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+/* -------------------------------------------------------------------------- */
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{panic_message, strip_panic, summary_table, DayReport, DayStatus};
+    use crate::template::fixtures::part_result;
+    use crate::{day, template::runner::PartResult};
+
+    #[test]
+    fn extracts_panic_messages() {
+        let stderr = "debug output\nthread 'main' panicked at src/bin/24.rs:10:5:\nnot yet implemented\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+        assert_eq!(panic_message(stderr), Some("not yet implemented".into()));
+        assert_eq!(strip_panic(stderr), "debug output\n");
+
+        let stderr = "thread 'main' panicked at 'index out of bounds', src/bin/01.rs:1:1\n";
+        assert_eq!(panic_message(stderr), Some("index out of bounds".into()));
+
+        assert_eq!(panic_message("Could not read input file."), None);
+    }
+
+    #[test]
+    fn prints_summary_table() {
+        let reports = [
+            DayReport {
+                day: day!(1),
+                results: vec![part_result(1, Some("1"), 0), part_result(2, None, 0)],
+                status: DayStatus::Unsolved,
+            },
+            DayReport {
+                day: day!(2),
+                results: vec![part_result(1, Some("1"), 0)],
+                status: DayStatus::Panicked("not yet implemented".into()),
+            },
+            DayReport {
+                day: day!(4),
+                results: vec![PartResult {
+                    error: Some("empty input".into()),
+                    ..part_result(1, None, 0)
+                }],
+                status: DayStatus::Errored(1, "empty input".into()),
+            },
+            DayReport {
+                day: day!(3),
+                results: vec![],
+                status: DayStatus::TimedOut(Duration::from_secs(60)),
+            },
+        ];
+
+        assert_eq!(
+            summary_table(&reports),
+            vec![
+                "| Day | Part 1 | Part 2 | Status |",
+                "| :---: | :---: | :---: | :--- |",
+                "| 01 | ✔ | ✖ | unsolved |",
+                "| 02 | ✔ | - | panicked: not yet implemented |",
+                "| 04 | ⚠ | - | part 1 failed: empty input |",
+                "| 03 | - | - | timed out after 60s |",
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_failures() {
+        assert!(!DayStatus::Ok.is_failure());
+        assert!(!DayStatus::Unsolved.is_failure());
+        assert!(!DayStatus::MissingInput.is_failure());
+        assert!(DayStatus::Errored(1, String::new()).is_failure());
+        assert!(DayStatus::Panicked(String::new()).is_failure());
+        assert!(DayStatus::TimedOut(Duration::ZERO).is_failure());
     }
 }
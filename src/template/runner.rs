@@ -1,66 +1,392 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{stdin, stdout, IsTerminal, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
+use tinyjson::JsonValue;
+
+use crate::template::alloc::{self, AllocStats};
+use crate::template::answers;
+use crate::template::aoc_client::{AocClient, AocClientError, SubmissionOutcome};
+use crate::template::memory::format_bytes;
+use crate::template::progress;
+use crate::template::submissions::{self, Submissions};
+use crate::template::unlocks::{self, Unlocks};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{read_file, Day, ANSI_ITALIC, ANSI_RESET};
+
+/// Default time a single day may take when run by `all` or `time` before it is killed, in seconds.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// Number of parts and parse steps of the current process that panicked, see `run_isolated`.
+static PANICS: AtomicUsize = AtomicUsize::new(0);
+
+/// Controls how `all` and `time` run a set of days.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Schedule {
+    /// Time a single day may take before it is killed.
+    pub timeout: Option<Duration>,
+    /// Number of days that run concurrently.
+    pub jobs: usize,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            timeout: Some(Duration::from_secs(DEFAULT_TIMEOUT_SECS)),
+            jobs: 1,
+        }
+    }
+}
+
+/// Options that control how a solution part is run.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Bench the part after its first execution.
+    pub is_timed: bool,
+    /// How results are written to stdout.
+    pub format: OutputFormat,
+}
+
+impl RunOptions {
+    /// Read the options from the arguments passed to a solution binary.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let format = match args.iter().position(|x| x == "--format") {
+            Some(index) => match args.get(index + 1).map(|x| x.parse()) {
+                Some(Ok(format)) => format,
+                _ => {
+                    eprintln!("Unexpected command-line input. Format: cargo solve 1 --format json");
+                    process::exit(1);
+                }
+            },
+            None => OutputFormat::default(),
+        };
+
+        Self {
+            is_timed: args.iter().any(|x| x == "--time"),
+            format,
+        }
+    }
+}
+
+/// Read the input passed to a solution binary with `--input <path>`, or `--input -` for stdin.
+/// Falls back to the puzzle input in `data/inputs`.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(index) = args.iter().position(|x| x == "--input") else {
+        return read_file("inputs", day);
+    };
+
+    let result = match args.get(index + 1).map(String::as_str) {
+        Some("-") => {
+            let mut input = String::new();
+            stdin().read_to_string(&mut input).map(|_| input)
+        }
+        Some(path) => fs::read_to_string(path),
+        None => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --input <path|->");
+            process::exit(1);
+        }
+    };
+
+    match result {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Could not read input: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Output format of the runner.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable results, styled with ANSI escape codes.
+    #[default]
+    Human,
+    /// One JSON record per part, separated by newlines.
+    Json,
+    /// No output, for callers that report results themselves.
+    Quiet,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(OutputFormat::Human),
+            "json" => Ok(OutputFormat::Json),
+            "quiet" => Ok(OutputFormat::Quiet),
+            _ => Err(format!(
+                "unknown output format `{s}`, expecting `human`, `json` or `quiet`"
+            )),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Human => write!(f, "human"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Quiet => write!(f, "quiet"),
+        }
+    }
+}
+
+/// Part number of the results emitted for the shared parse step of a day.
+pub const PARSE_STEP: u8 = 0;
+
+/// The outcome of running a single solution part, or the parse step if `part` is [`PARSE_STEP`].
+/// `duration` is the mean of the bench samples when the part was benched, its single execution time otherwise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartResult {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Message of the error returned by the part, if it returned an `Err`.
+    pub error: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+    pub stats: Option<BenchStats>,
+    /// Allocations of the first execution, if built with the `count-allocs` feature.
+    pub allocs: Option<AllocStats>,
+}
+
+/// Summary statistics of the samples collected while benching a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchStats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+    /// Number of samples rejected as outliers.
+    pub outliers: u128,
+}
+
+/// Return type of a solution part: either `Option<T>`, where `None` means that no answer was found, or
+/// `Result<T, E>`, where `Err` is reported with its message.
+pub trait PartOutput {
+    /// The answer of the part, or the message of its error.
+    fn to_answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().map(ToString::to_string))
+    }
+}
+
+impl<T: Display, E: Display> PartOutput for Result<T, E> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        match self {
+            Ok(answer) => Ok(Some(answer.to_string())),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) -> PartResult {
     let part_str = format!("Part {part}");
+    let is_human = options.format == OutputFormat::Human;
+
+    let (result, duration, samples, stats, allocs) =
+        run_timed(func, input, options, &part_str, |result| {
+            if is_human {
+                print_result(&result.to_answer(), &part_str);
+            }
+        });
+
+    let (answer, error) = match result.to_answer() {
+        Ok(answer) => (answer, None),
+        Err(e) => (None, Some(e)),
+    };
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let part_result = PartResult {
+        day,
+        part,
+        answer,
+        error,
+        duration,
+        samples,
+        stats,
+        allocs,
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_part_result(&part_result, options.format);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(answer) = &part_result.answer {
+        submit_result(answer, day, part);
     }
+
+    part_result
+}
+
+/// Run the shared parse step of a solution. Returns the parsed input together with the timing of the step.
+pub fn run_parse<'a, P>(
+    func: impl Fn(&'a str) -> P,
+    input: &'a str,
+    day: Day,
+    options: &RunOptions,
+) -> (P, PartResult) {
+    let is_human = options.format == OutputFormat::Human;
+
+    let (parsed, duration, samples, stats, allocs) =
+        run_timed(func, input, options, "Parse", |_| {
+            if is_human {
+                print!("Parse:");
+                let _ = stdout().flush();
+            }
+        });
+
+    let parse_result = PartResult {
+        day,
+        part: PARSE_STEP,
+        answer: None,
+        error: None,
+        duration,
+        samples,
+        stats,
+        allocs,
+    };
+
+    print_part_result(&parse_result, options.format);
+
+    (parsed, parse_result)
+}
+
+/// Run a part or the parse step of a solution, so that a panic only stops this step and the other parts still run.
+/// The panic message is printed by the panic hook as usual, and returned as the error if the step panicked.
+pub fn run_isolated<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(func)).map_err(|payload| {
+        progress::finish();
+        PANICS.fetch_add(1, Ordering::Relaxed);
+        panic_message(payload.as_ref())
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".into())
+}
+
+/// Report that the parse step of a solution panicked with `message`. The parts can not run without its output, so
+/// this record is the only result of the day.
+pub fn parse_failed(day: Day, message: &str, options: &RunOptions) -> PartResult {
+    let parse_result = PartResult {
+        day,
+        part: PARSE_STEP,
+        answer: None,
+        error: Some(format!("panicked: {message}")),
+        duration: Duration::ZERO,
+        samples: 0,
+        stats: None,
+        allocs: None,
+    };
+
+    print_part_result(&parse_result, options.format);
+    parse_result
+}
+
+/// Number of steps run with `run_isolated` that panicked so far.
+pub fn panic_count() -> usize {
+    PANICS.load(Ordering::Relaxed)
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Allocations are only counted for the first execution, so benching does not skew them. Progress is only shown when
+/// the part is not timed, as drawing it would be measured as part of the solution.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    options: &RunOptions,
+    label: &str,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<BenchStats>, Option<AllocStats>) {
+    let show_progress =
+        !options.is_timed && options.format == OutputFormat::Human && stdout().is_terminal();
+    if show_progress {
+        progress::start(label);
+    }
+
     let timer = Instant::now();
-    let result = {
+    let (result, allocs) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        alloc::count(|| func(input))
     };
     let base_time = timer.elapsed();
 
+    if show_progress {
+        progress::finish();
+    }
+
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if options.is_timed {
+        let (stats, samples) = bench(
+            func,
+            input,
+            &base_time,
+            options.format == OutputFormat::Human,
+        );
+        (result, stats.mean, samples, Some(stats), allocs)
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, 1, None, allocs)
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    is_human: bool,
+) -> (BenchStats, u128) {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    if is_human {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    // warm up caches and branch predictors before measuring.
+    for _ in 0..(bench_iterations / 10).clamp(1, 100) {
+        black_box(func(black_box(input.clone())));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -71,14 +397,79 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    (bench_stats(&timers), bench_iterations)
+}
+
+/// Summarize bench samples. Outliers outside of the Tukey fences (1.5 times the interquartile range) are
+/// rejected before computing mean, median, extrema, standard deviation and 95th percentile.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn bench_stats(timers: &[Duration]) -> BenchStats {
+    let mut sorted = timers.to_vec();
+    sorted.sort_unstable();
+
+    let q1 = percentile(&sorted, 25.0).as_nanos() as f64;
+    let q3 = percentile(&sorted, 75.0).as_nanos() as f64;
+    let fence = 1.5 * (q3 - q1);
+
+    let kept: Vec<Duration> = sorted
+        .iter()
+        .filter(|x| {
+            let nanos = x.as_nanos() as f64;
+            nanos >= q1 - fence && nanos <= q3 + fence
+        })
+        .copied()
+        .collect();
+
+    let mean = average_duration(&kept);
+    let variance = kept
+        .iter()
+        .map(|x| (x.as_nanos() as f64 - mean as f64).powi(2))
+        .sum::<f64>()
+        / kept.len() as f64;
+
+    BenchStats {
+        mean: Duration::from_nanos(mean as u64),
+        median: median(&kept),
+        min: kept.first().copied().unwrap_or_default(),
+        max: kept.last().copied().unwrap_or_default(),
+        stddev: Duration::from_nanos(variance.sqrt() as u64),
+        p95: percentile(&kept, 95.0),
+        outliers: (sorted.len() - kept.len()) as u128,
+    }
+}
+
+/// Nearest-rank percentile of a sorted slice.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Median of a sorted slice.
+fn median(sorted: &[Duration]) -> Duration {
+    let len = sorted.len();
+    match len {
+        0 => Duration::ZERO,
+        _ if len % 2 == 1 => sorted[len / 2],
+        _ => (sorted[len / 2 - 1] + sorted[len / 2]) / 2,
+    }
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
+    if numbers.is_empty() {
+        return 0;
+    }
     numbers
         .iter()
         .map(std::time::Duration::as_nanos)
@@ -86,57 +477,107 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_duration(duration: &Duration, samples: u128, stats: Option<&BenchStats>) -> String {
+    match stats {
+        Some(stats) if samples > 1 => format!(
+            " ({duration:.1?} @ {samples} samples, median {:.1?}, min {:.1?}, max {:.1?}, σ {:.1?}, p95 {:.1?})",
+            stats.median, stats.min, stats.max, stats.stddev, stats.p95
+        ),
+        _ if samples == 1 => format!(" ({duration:.1?})"),
+        _ => format!(" ({duration:.1?} @ {samples} samples)"),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
+fn format_allocs(allocs: Option<&AllocStats>) -> String {
+    match allocs {
+        Some(allocs) => format!(
+            " [{} allocs, {}, peak {}]",
+            allocs.allocations,
+            format_bytes(allocs.bytes),
+            format_bytes(allocs.peak_bytes)
+        ),
+        None => String::new(),
+    }
+}
 
+/// Print the answer of a part before its duration is known.
+fn print_result(result: &Result<Option<String>, String>, part: &str) {
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
+        Ok(Some(answer)) if answer.contains('\n') => print!("{part}: ▼ "),
+        Ok(Some(answer)) => print!("{part}: {ANSI_BOLD}{answer}{ANSI_RESET}"),
+        Ok(None) => print!("{part}: ✖"),
+        Err(_) => print!("{part}: ⚠"),
+    }
+}
+
+/// Format the answer of a part together with its duration, overwriting the line printed by `print_result`.
+fn format_result(answer: Option<&str>, part: &str, duration_str: &str) -> String {
+    match answer {
+        Some(answer) if answer.contains('\n') => {
+            format!("\r{part}: ▼ {duration_str}\n{answer}\n")
         }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
+        Some(answer) => format!("\r{part}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration_str}\n"),
+        None => format!("\r{part}: ✖             \n"),
+    }
+}
+
+/// Format the error returned by a part, overwriting the line printed by `print_result`.
+fn format_error(error: &str, part: &str, duration_str: &str) -> String {
+    format!("\r{part}: ⚠ {ANSI_ITALIC}{error}{ANSI_RESET}{duration_str}\n")
+}
+
+/// Format a finished part result, or parse step, in the given format.
+pub fn format_part_result(result: &PartResult, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Human => {
+            // NOTE: a step without samples did not finish, e.g. a parse step that panicked.
+            let duration_str = if result.samples == 0 {
+                String::new()
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                format!(
+                    "{}{}",
+                    format_duration(&result.duration, result.samples, result.stats.as_ref()),
+                    format_allocs(result.allocs.as_ref())
+                )
+            };
+            let label = match result.part {
+                PARSE_STEP => "Parse".to_string(),
+                part => format!("Part {part}"),
+            };
+            if let Some(error) = &result.error {
+                format_error(error, &label, &duration_str)
+            } else if result.part == PARSE_STEP {
+                format!("\rParse:{duration_str}\n")
+            } else {
+                format_result(result.answer.as_deref(), &label, &duration_str)
             }
         }
+        OutputFormat::Json => match JsonValue::from(result).stringify() {
+            Ok(line) => format!("{line}\n"),
+            Err(e) => {
+                eprintln!("Could not serialize result: {e}");
+                String::new()
+            }
+        },
+        OutputFormat::Quiet => String::new(),
     }
 }
 
+/// Print a finished part result, or parse step, in the given format.
+fn print_part_result(result: &PartResult, format: OutputFormat) {
+    print!("{}", format_part_result(result, format));
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///  3. the submission log in `data/submissions.json` does not rule out the answer.
+///  4. the solution ran against the puzzle input, not one passed with `--input`.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmissionOutcome, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +600,429 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if args.contains(&"--input".into()) {
+        eprintln!("Not submitting result: the answer was computed for a custom input.");
+        return None;
+    }
+
+    let answer = result.to_string();
+    let mut submissions = Submissions::read_from_file();
+
+    if let Err(refusal) = submissions.check(day, part, &answer, submissions::now()) {
+        eprintln!("Not submitting result: {refusal}");
+        return None;
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to configure client: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("Submitting result...");
+    let outcome = client.submit(day, part, &answer);
+
+    match &outcome {
+        Ok(outcome) => {
+            println!("{outcome}");
+
+            submissions.record(day, part, &answer, outcome, submissions::now());
+            if let Err(e) = submissions.store_file() {
+                eprintln!("Failed to store submission: {e}");
+            }
+
+            if *outcome == SubmissionOutcome::Correct {
+                if let Err(e) = answers::record(day, part, &answer) {
+                    eprintln!("Failed to record answer: {e}");
+                }
+
+                let unlocks = Unlocks::read_from_file();
+                if let Some(seconds) = unlocks.time_to_solve(day, submissions::now()) {
+                    println!(
+                        "🎄 Solved part {part} {} after the puzzle unlocked.",
+                        unlocks::format_seconds(seconds)
+                    );
+                }
+            }
+        }
+        Err(e) => eprintln!("Failed to submit result: {e}"),
+    }
+
+    Some(outcome)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "allocs".into(),
+            match &value.allocs {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        // NOTE: records without an error are valid, e.g. when written by an older version.
+        let error = match json.get("error") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected record.error to be null or string.")?
+                    .clone(),
+            ),
+        };
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.samples to be a number.")?;
+
+        // NOTE: records without stats are valid, e.g. for parts that were not benched.
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        let allocs = match json.get("allocs") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(AllocStats::try_from(v)?),
+        };
+
+        Ok(PartResult {
+            day,
+            part: part as u8,
+            answer: answer.cloned(),
+            error,
+            duration: Duration::from_nanos(nanos as u64),
+            samples: samples as u128,
+            stats,
+            allocs,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let durations = [
+            ("mean_nanos", value.mean),
+            ("median_nanos", value.median),
+            ("min_nanos", value.min),
+            ("max_nanos", value.max),
+            ("stddev_nanos", value.stddev),
+            ("p95_nanos", value.p95),
+        ];
+
+        for (key, duration) in durations {
+            map.insert(key.into(), JsonValue::Number(duration.as_nanos() as f64));
+        }
+
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        let duration = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+        Ok(BenchStats {
+            mean: duration("mean_nanos")?,
+            median: duration("median_nanos")?,
+            min: duration("min_nanos")?,
+            max: duration("max_nanos")?,
+            stddev: duration("stddev_nanos")?,
+            p95: duration("p95_nanos")?,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+impl FromStr for PartResult {
+    type Err = String;
+
+    /// Parse a single JSON-lines record, as written with `--format json`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not a valid JSON record."))?;
+        PartResult::try_from(&json)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{
+        bench_stats, format_part_result, parse_failed, run_isolated, BenchStats, OutputFormat,
+        PartOutput, PartResult, RunOptions, PARSE_STEP,
+    };
+    use crate::day;
+    use crate::template::alloc::AllocStats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn parses_output_format() {
+        assert_eq!("json".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+        assert_eq!("human".parse::<OutputFormat>(), Ok(OutputFormat::Human));
+        assert_eq!("quiet".parse::<OutputFormat>(), Ok(OutputFormat::Quiet));
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn parses_records() {
+        let record: PartResult =
+            r#"{ "day": "01", "part": 2, "answer": "42", "nanos": 74130, "samples": 100 }"#
+                .parse()
+                .unwrap();
+        assert_eq!(record.day, day!(1));
+        assert_eq!(record.part, 2);
+        assert_eq!(record.answer, Some("42".into()));
+        assert_eq!(record.duration, Duration::from_nanos(74130));
+        assert_eq!(record.samples, 100);
+        assert_eq!(record.stats, None);
+        assert_eq!(record.allocs, None);
+    }
+
+    #[test]
+    fn parses_records_without_answer() {
+        let record: PartResult =
+            r#"{ "day": "25", "part": 1, "answer": null, "nanos": 0, "samples": 1 }"#
+                .parse()
+                .unwrap();
+        assert_eq!(record.answer, None);
+    }
+
+    #[test]
+    fn roundtrips_records() {
+        let record = PartResult {
+            day: day!(7),
+            part: 1,
+            answer: Some("Part 1: 10 (2s @ 5 samples)".into()),
+            error: None,
+            duration: Duration::from_nanos(123_456),
+            samples: 10,
+            stats: Some(BenchStats {
+                mean: Duration::from_nanos(123_456),
+                median: Duration::from_nanos(120_000),
+                min: Duration::from_nanos(100_000),
+                max: Duration::from_nanos(200_000),
+                stddev: Duration::from_nanos(5_000),
+                p95: Duration::from_nanos(190_000),
+                outliers: 2,
+            }),
+            allocs: Some(AllocStats {
+                allocations: 12,
+                bytes: 4096,
+                peak_bytes: 1536,
+            }),
+        };
+        let line = JsonValue::from(&record).stringify().unwrap();
+        assert_eq!(line.contains('\n'), false);
+        assert_eq!(line.parse::<PartResult>().unwrap(), record);
+    }
+
+    #[test]
+    fn formats_allocs() {
+        let record = PartResult {
+            day: day!(7),
+            part: 2,
+            answer: Some("10".into()),
+            error: None,
+            duration: Duration::from_micros(12),
+            samples: 1,
+            stats: None,
+            allocs: Some(AllocStats {
+                allocations: 3,
+                bytes: 2048,
+                peak_bytes: 512,
+            }),
+        };
+        assert!(format_part_result(&record, OutputFormat::Human)
+            .ends_with("(12.0µs) [3 allocs, 2.0 KiB, peak 512 B]\n"));
+    }
+
+    #[test]
+    fn converts_part_outputs() {
+        assert_eq!(Some(42).to_answer(), Ok(Some("42".into())));
+        assert_eq!(None::<u32>.to_answer(), Ok(None));
+        assert_eq!(Ok::<u32, &str>(42).to_answer(), Ok(Some("42".into())));
+        assert_eq!(
+            Err::<u32, &str>("invalid digit").to_answer(),
+            Err("invalid digit".into())
+        );
+    }
+
+    #[test]
+    fn roundtrips_errors() {
+        let record = PartResult {
+            day: day!(3),
+            part: 2,
+            answer: None,
+            error: Some("unexpected token `x` on line 4".into()),
+            duration: Duration::from_nanos(500),
+            samples: 1,
+            stats: None,
+            allocs: None,
+        };
+        let line = JsonValue::from(&record).stringify().unwrap();
+        assert_eq!(line.parse::<PartResult>().unwrap(), record);
+        assert!(format_part_result(&record, OutputFormat::Human)
+            .contains("Part 2: ⚠ \x1b[3munexpected token `x` on line 4"));
+    }
+
+    #[test]
+    fn isolates_panics() {
+        assert_eq!(run_isolated(|| 42), Ok(42));
+        assert_eq!(
+            run_isolated(|| -> u32 { panic!("bad input on line {}", 3) }),
+            Err("bad input on line 3".into())
+        );
+    }
+
+    #[test]
+    fn reports_failed_parse_steps() {
+        let options = RunOptions {
+            is_timed: false,
+            format: OutputFormat::Quiet,
+        };
+        let record = parse_failed(day!(4), "empty grid", &options);
+        assert_eq!(record.part, PARSE_STEP);
+        assert_eq!(record.error, Some("panicked: empty grid".into()));
+
+        let line = JsonValue::from(&record).stringify().unwrap();
+        assert_eq!(line.parse::<PartResult>().unwrap(), record);
+        assert_eq!(
+            format_part_result(&record, OutputFormat::Human),
+            "\rParse: ⚠ \x1b[3mpanicked: empty grid\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn computes_bench_stats() {
+        let stats = bench_stats(&nanos(&[50, 10, 40, 20, 30]));
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(50));
+        assert_eq!(stats.stddev, Duration::from_nanos(14));
+        assert_eq!(stats.p95, Duration::from_nanos(50));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = bench_stats(&nanos(&[10, 11, 10, 12, 11, 10, 11, 1000]));
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, Duration::from_nanos(12));
+        assert_eq!(stats.median, Duration::from_nanos(11));
+        assert_eq!(stats.mean, Duration::from_nanos(10));
+    }
+
+    #[test]
+    fn computes_even_median() {
+        let stats = bench_stats(&nanos(&[10, 20, 30, 40]));
+        assert_eq!(stats.median, Duration::from_nanos(25));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_records() {
+        r#"{ "day": "01", "part": 1 }"#.parse::<PartResult>().unwrap();
+    }
 }
@@ -0,0 +1,452 @@
+/// Module that remembers submitted answers and the verdicts of the server.
+/// Before submitting, the log is consulted so that known-wrong answers, answers outside of learned bounds
+/// and answers during a cooldown are not sent to the server again.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{AnswerHint, SubmissionOutcome};
+use crate::template::Day;
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.json";
+
+/// Verdict of the server for an attempt, as persisted in the log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    TooRecent,
+    AlreadyCompleted,
+    Unknown,
+}
+
+impl From<&SubmissionOutcome> for Verdict {
+    fn from(value: &SubmissionOutcome) -> Self {
+        match value {
+            SubmissionOutcome::Correct => Verdict::Correct,
+            SubmissionOutcome::Incorrect { hint, .. } => match hint {
+                Some(AnswerHint::TooHigh) => Verdict::TooHigh,
+                Some(AnswerHint::TooLow) => Verdict::TooLow,
+                None => Verdict::Incorrect,
+            },
+            SubmissionOutcome::TooRecent { .. } => Verdict::TooRecent,
+            SubmissionOutcome::AlreadyCompleted => Verdict::AlreadyCompleted,
+            SubmissionOutcome::Unknown(_) => Verdict::Unknown,
+        }
+    }
+}
+
+impl Verdict {
+    /// Whether the server rejected the answer itself.
+    fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::TooRecent => "too_recent",
+            Verdict::AlreadyCompleted => "already_completed",
+            Verdict::Unknown => "unknown",
+        };
+        write!(f, "{value}")
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "too_recent" => Ok(Verdict::TooRecent),
+            "already_completed" => Ok(Verdict::AlreadyCompleted),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attempt {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Reason why an answer is not submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was already solved with this answer.
+    AlreadyCorrect,
+    /// Exactly this answer was rejected before.
+    KnownWrong,
+    /// The answer is at or above an answer that was too high.
+    TooHigh { bound: i128 },
+    /// The answer is at or below an answer that was too low.
+    TooLow { bound: i128 },
+    /// The server asked to wait before submitting again.
+    Cooldown { remaining: Duration },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect => write!(f, "this answer was already accepted."),
+            Refusal::KnownWrong => write!(f, "this answer was already rejected."),
+            Refusal::TooHigh { bound } => write!(
+                f,
+                "this answer is too high, {bound} was already rejected as too high."
+            ),
+            Refusal::TooLow { bound } => write!(
+                f,
+                "this answer is too low, {bound} was already rejected as too low."
+            ),
+            Refusal::Cooldown { remaining } => write!(
+                f,
+                "the server asked to wait, try again in {}s.",
+                remaining.as_secs()
+            ),
+        }
+    }
+}
+
+/// Log of all submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Submissions {
+    pub data: Vec<Attempt>,
+    /// Seconds since the unix epoch until which the server refuses submissions.
+    pub locked_until: Option<u64>,
+}
+
+impl Submissions {
+    /// Dehydrate the log to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(SUBMISSIONS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the log from a JSON file. If not present, returns an empty log.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(SUBMISSIONS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Submissions::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Check whether `answer` should be submitted for a part at time `now`.
+    pub fn check(&self, day: Day, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let attempts = || {
+            self.data
+                .iter()
+                .filter(move |a| a.day == day && a.part == part)
+        };
+
+        if let Some(attempt) = attempts().find(|a| a.answer == answer) {
+            match attempt.verdict {
+                Verdict::Correct => return Err(Refusal::AlreadyCorrect),
+                verdict if verdict.is_wrong() => return Err(Refusal::KnownWrong),
+                _ => {}
+            }
+        }
+
+        if let Ok(value) = answer.trim().parse::<i128>() {
+            let bound = |verdict: Verdict| {
+                attempts()
+                    .filter(move |a| a.verdict == verdict)
+                    .filter_map(|a| a.answer.trim().parse::<i128>().ok())
+            };
+
+            if let Some(bound) = bound(Verdict::TooHigh).min() {
+                if value >= bound {
+                    return Err(Refusal::TooHigh { bound });
+                }
+            }
+
+            if let Some(bound) = bound(Verdict::TooLow).max() {
+                if value <= bound {
+                    return Err(Refusal::TooLow { bound });
+                }
+            }
+        }
+
+        match self.locked_until {
+            Some(until) if until > now => Err(Refusal::Cooldown {
+                remaining: Duration::from_secs(until - now),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Add an attempt and its outcome to the log, updating the cooldown if the server reported one.
+    pub fn record(
+        &mut self,
+        day: Day,
+        part: u8,
+        answer: &str,
+        outcome: &SubmissionOutcome,
+        now: u64,
+    ) {
+        self.data.push(Attempt {
+            day,
+            part,
+            answer: answer.into(),
+            verdict: Verdict::from(outcome),
+            timestamp: now,
+        });
+
+        let wait = match outcome {
+            SubmissionOutcome::Incorrect { wait, .. } | SubmissionOutcome::TooRecent { wait } => {
+                *wait
+            }
+            _ => None,
+        };
+
+        self.locked_until = wait.map(|wait| now + wait.as_secs());
+    }
+}
+
+/// Current time in seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|x| x.as_secs())
+        .unwrap_or_default()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        map.insert(
+            "locked_until".into(),
+            match value.locked_until {
+                Some(x) => JsonValue::Number(x as f64),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        let locked_until = json
+            .get("locked_until")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64);
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Attempt::try_from)
+                .collect::<Result<_, _>>()?,
+            locked_until,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Attempt> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Attempt) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Attempt {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected attempt to be a JSON object.")?;
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>());
+        let number = |key: &str| json.get(key).and_then(|v| v.get::<f64>()).copied();
+
+        Ok(Attempt {
+            day: string("day")
+                .and_then(|day| Day::from_str(day).ok())
+                .ok_or("Expected attempt.day to be a Day struct.")?,
+            part: number("part").ok_or("Expected attempt.part to be a number.")? as u8,
+            answer: string("answer")
+                .cloned()
+                .ok_or("Expected attempt.answer to be a string.")?,
+            verdict: string("verdict")
+                .ok_or("Expected attempt.verdict to be a string.")?
+                .parse()?,
+            timestamp: number("timestamp").ok_or("Expected attempt.timestamp to be a number.")?
+                as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{Refusal, Submissions, Verdict};
+    use crate::{
+        day,
+        template::aoc_client::{AnswerHint, SubmissionOutcome},
+    };
+
+    fn incorrect(hint: Option<AnswerHint>) -> SubmissionOutcome {
+        SubmissionOutcome::Incorrect { hint, wait: None }
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "42", &incorrect(None), 0);
+        submissions.record(day!(1), 2, "7", &SubmissionOutcome::Correct, 0);
+
+        assert_eq!(
+            submissions.check(day!(1), 1, "42", 0),
+            Err(Refusal::KnownWrong)
+        );
+        assert_eq!(
+            submissions.check(day!(1), 2, "7", 0),
+            Err(Refusal::AlreadyCorrect)
+        );
+        assert_eq!(submissions.check(day!(1), 1, "43", 0), Ok(()));
+        assert_eq!(submissions.check(day!(2), 1, "42", 0), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_of_bounds() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "100", &incorrect(Some(AnswerHint::TooHigh)), 0);
+        submissions.record(day!(1), 1, "80", &incorrect(Some(AnswerHint::TooHigh)), 0);
+        submissions.record(day!(1), 1, "10", &incorrect(Some(AnswerHint::TooLow)), 0);
+
+        assert_eq!(
+            submissions.check(day!(1), 1, "90", 0),
+            Err(Refusal::TooHigh { bound: 80 })
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "-5", 0),
+            Err(Refusal::TooLow { bound: 10 })
+        );
+        assert_eq!(submissions.check(day!(1), 1, "50", 0), Ok(()));
+        assert_eq!(submissions.check(day!(1), 1, "abc", 0), Ok(()));
+        assert_eq!(submissions.check(day!(1), 2, "90", 0), Ok(()));
+    }
+
+    #[test]
+    fn respects_cooldowns() {
+        let mut submissions = Submissions::default();
+        submissions.record(
+            day!(1),
+            1,
+            "42",
+            &SubmissionOutcome::Incorrect {
+                hint: None,
+                wait: Some(Duration::from_secs(60)),
+            },
+            1_000,
+        );
+
+        assert_eq!(
+            submissions.check(day!(3), 1, "1", 1_030),
+            Err(Refusal::Cooldown {
+                remaining: Duration::from_secs(30)
+            })
+        );
+        assert_eq!(submissions.check(day!(3), 1, "1", 1_060), Ok(()));
+
+        submissions.record(day!(3), 1, "1", &SubmissionOutcome::Correct, 1_060);
+        assert_eq!(submissions.locked_until, None);
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let mut submissions = Submissions::default();
+        submissions.record(
+            day!(4),
+            2,
+            "12",
+            &SubmissionOutcome::TooRecent {
+                wait: Some(Duration::from_secs(5)),
+            },
+            10,
+        );
+        submissions.record(day!(4), 2, "13", &incorrect(Some(AnswerHint::TooLow)), 20);
+
+        let json = JsonValue::from(submissions.clone()).stringify().unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed, submissions);
+        assert_eq!(parsed.data[1].verdict, Verdict::TooLow);
+    }
+}
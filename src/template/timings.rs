@@ -1,6 +1,8 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
+use crate::template::memory::MemoryStats;
+use crate::template::runner::{BenchStats, PartResult, PARSE_STEP};
 use crate::template::Day;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
@@ -11,9 +13,77 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    /// Duration of the shared parse step, if the day has one.
+    pub parse: Option<String>,
+    pub parse_stats: Option<BenchStats>,
+    /// Heap usage of the day, if it was profiled with `cargo time --memory`.
+    pub memory: Option<MemoryStats>,
     pub total_nanos: f64,
 }
 
+/// An empty timing, so a timing can be written as e.g. `Timing { day, ..Default::default() }`.
+/// The day defaults to the first one.
+impl Default for Timing {
+    fn default() -> Self {
+        Self {
+            day: crate::day!(1),
+            part_1: None,
+            part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse: None,
+            parse_stats: None,
+            memory: None,
+            total_nanos: 0_f64,
+        }
+    }
+}
+
+impl Timing {
+    /// Collect the timings of a day from the records emitted by the runner.
+    /// Parts without an answer are not recorded, the parse step is always recorded.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_results(day: Day, results: &[PartResult]) -> Self {
+        let mut timing = Timing {
+            day,
+            ..Default::default()
+        };
+
+        results
+            .iter()
+            .filter(|result| {
+                result.day == day
+                    && (result.answer.is_some()
+                        || (result.part == PARSE_STEP && result.error.is_none()))
+            })
+            .for_each(|result| {
+                let duration = Some(format!("{:.1?}", result.duration));
+
+                match result.part {
+                    PARSE_STEP => {
+                        timing.parse = duration;
+                        timing.parse_stats = result.stats;
+                    }
+                    1 => {
+                        timing.part_1 = duration;
+                        timing.part_1_stats = result.stats;
+                    }
+                    2 => {
+                        timing.part_2 = duration;
+                        timing.part_2_stats = result.stats;
+                    }
+                    _ => {}
+                }
+
+                timing.total_nanos += result.duration.as_nanos() as f64;
+            });
+
+        timing
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -42,7 +112,16 @@ impl Timings {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            // keep the heap usage of an earlier `--memory` run if the new timing was not profiled.
+            if timing.memory.is_none() {
+                timing.memory = self
+                    .data
+                    .iter()
+                    .find(|t| t.day == timing.day)
+                    .and_then(|t| t.memory);
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -133,6 +212,26 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert("part_1_stats".into(), stats_to_json(value.part_1_stats));
+        map.insert("part_2_stats".into(), stats_to_json(value.part_2_stats));
+
+        map.insert(
+            "parse".into(),
+            match value.parse.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("parse_stats".into(), stats_to_json(value.parse_stats));
+
+        map.insert(
+            "memory".into(),
+            match &value.memory {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -161,6 +260,21 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        // NOTE: timings stored before parse steps were introduced do not have this key.
+        let parse = match json.get("parse") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+            _ => None,
+        };
+
+        // NOTE: only days profiled with `--memory` have this key.
+        let memory = match json.get("memory") {
+            Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+            _ => None,
+        };
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
@@ -170,14 +284,35 @@ impl TryFrom<&JsonValue> for Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats: stats_from_json(json.get("part_1_stats"))?,
+            part_2_stats: stats_from_json(json.get("part_2_stats"))?,
+            parse: parse.cloned(),
+            parse_stats: stats_from_json(json.get("parse_stats"))?,
+            memory,
             total_nanos,
         })
     }
 }
 
+fn stats_to_json(stats: Option<BenchStats>) -> JsonValue {
+    match stats {
+        Some(x) => JsonValue::from(&x),
+        None => JsonValue::Null,
+    }
+}
+
+/// Stats are optional, timings stored before they were collected do not have them.
+fn stats_from_json(value: Option<&JsonValue>) -> Result<Option<BenchStats>, String> {
+    match value {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => BenchStats::try_from(v).map(Some),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
     use crate::day;
 
@@ -191,18 +326,20 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
                     total_nanos: 4e+10,
+                    ..Default::default()
                 },
             ],
         }
@@ -223,6 +360,18 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "mean_nanos": 1000000, "median_nanos": 900000, "min_nanos": 800000, "max_nanos": 1500000, "stddev_nanos": 1000, "p95_nanos": 1400000, "outliers": 3 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.unwrap();
+            assert_eq!(stats.median.as_nanos(), 900_000);
+            assert_eq!(stats.p95.as_nanos(), 1_400_000);
+            assert_eq!(stats.outliers, 3);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -282,10 +431,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -294,12 +444,12 @@ mod tests {
                 data: vec![Timing {
                     day: day!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,19 +457,80 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: None,
-                    part_2: None,
                     total_nanos: 0.0,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod from_results {
+        use crate::{
+            day,
+            template::{fixtures::part_result, runner::PartResult, timings::Timing},
+        };
+
+        #[test]
+        fn collects_execution_times() {
+            let res = Timing::from_results(
+                day!(1),
+                &[
+                    part_result(1, Some("0"), 74_130),
+                    part_result(2, Some("10"), 74_130_000),
+                ],
+            );
+            assert_eq!(res.total_nanos, 74_204_130_f64);
+            assert_eq!(res.part_1.unwrap(), "74.1µs");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+        }
+
+        #[test]
+        fn collects_records() {
+            let records = [
+                r#"{ "day": "01", "part": 1, "answer": "@ @ ( ) ms", "nanos": 2000000000, "samples": 5 }"#,
+                r#"{ "day": "01", "part": 2, "answer": "10s", "nanos": 100000000, "samples": 1 }"#,
+            ]
+            .map(|line| line.parse::<PartResult>().unwrap());
+
+            let res = Timing::from_results(day!(1), &records);
+            assert_eq!(res.total_nanos, 2_100_000_000_f64);
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
+        }
+
+        #[test]
+        fn skips_missing_parts() {
+            let res = Timing::from_results(
+                day!(1),
+                &[part_result(1, None, 100), part_result(2, None, 100)],
+            );
+            assert_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn collects_parse_step() {
+            let res = Timing::from_results(
+                day!(1),
+                &[
+                    part_result(0, None, 1_000),
+                    part_result(1, Some("0"), 2_000),
+                    part_result(2, Some("10"), 3_000),
+                ],
+            );
+            assert_eq!(res.total_nanos, 6_000_f64);
+            assert_eq!(res.parse.unwrap(), "1.0µs");
+            assert_eq!(res.part_1.unwrap(), "2.0µs");
         }
     }
 
     mod merge {
         use crate::{
             day,
+            template::memory::MemoryStats,
             template::timings::{Timing, Timings},
         };
 
@@ -331,9 +542,7 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -351,9 +560,7 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -365,6 +572,27 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_stored_memory() {
+            let mut timings = get_mock_timings();
+            let memory = MemoryStats {
+                peak_bytes: 1024,
+                peak_blocks: 1,
+                total_bytes: 2048,
+                total_blocks: 3,
+            };
+            timings.data[1].memory = Some(memory);
+
+            let mut other = Timings {
+                data: vec![timings.data[1].clone(), timings.data[0].clone()],
+            };
+            other.data[0].memory = None;
+
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data[0].memory, None);
+            assert_eq!(merged.data[1].memory, Some(memory));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -0,0 +1,163 @@
+/// Module that records when puzzles unlocked, as seen by `cargo today --wait`.
+/// Together with the submission log, this allows computing how long it took to solve a part.
+use std::{collections::BTreeMap, collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static UNLOCKS_FILE_PATH: &str = "./data/unlocks.json";
+
+/// Unlock times by day, in seconds since the unix epoch.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Unlocks {
+    pub data: BTreeMap<Day, u64>,
+}
+
+impl Unlocks {
+    /// Dehydrate unlock times to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(UNLOCKS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate unlock times from a JSON file. If not present, returns empty unlock times.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(UNLOCKS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Unlocks::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Seconds between the unlock of a day and `solved_at`, if its unlock time is known.
+    #[must_use]
+    pub fn time_to_solve(&self, day: Day, solved_at: u64) -> Option<u64> {
+        solved_at.checked_sub(*self.data.get(&day)?)
+    }
+}
+
+/// Record the unlock time of a day in the unlocks file.
+pub fn record(day: Day, timestamp: u64) -> Result<(), Error> {
+    let mut unlocks = Unlocks::read_from_file();
+    unlocks.data.insert(day, timestamp);
+    unlocks.store_file()
+}
+
+/// Format a number of seconds as `HH:MM:SS`, prefixed with the number of days if there are any.
+#[must_use]
+pub fn format_seconds(seconds: u64) -> String {
+    let (days, seconds) = (seconds / 86_400, seconds % 86_400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Unlocks> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Unlocks) -> Self {
+        let data = value
+            .data
+            .iter()
+            .map(|(day, timestamp)| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("day".into(), JsonValue::String(day.to_string()));
+                map.insert("unlocked_at".into(), JsonValue::Number(*timestamp as f64));
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("data".into(), JsonValue::Array(data));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Unlocks {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        let mut unlocks = Unlocks::default();
+
+        for entry in json_data {
+            let entry = entry
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected unlock to be a JSON object.")?;
+
+            let day = entry
+                .get("day")
+                .and_then(|v| v.get::<String>())
+                .and_then(|day| Day::from_str(day).ok())
+                .ok_or("Expected unlock.day to be a Day struct.")?;
+
+            let timestamp = entry
+                .get("unlocked_at")
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or("Expected unlock.unlocked_at to be a number.")?;
+
+            unlocks.data.insert(day, timestamp as u64);
+        }
+
+        Ok(unlocks)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_seconds, Unlocks};
+    use crate::day;
+
+    #[test]
+    fn roundtrips_unlocks() {
+        let mut unlocks = Unlocks::default();
+        // 2024-12-01 and 2024-12-02, 00:00 EST.
+        unlocks.data.insert(day!(1), 1_733_029_200);
+        unlocks.data.insert(day!(2), 1_733_115_600);
+
+        let json = JsonValue::from(&unlocks).stringify().unwrap();
+        assert_eq!(Unlocks::try_from(json), Ok(unlocks));
+    }
+
+    #[test]
+    fn computes_time_to_solve() {
+        let mut unlocks = Unlocks::default();
+        unlocks.data.insert(day!(1), 1_733_029_200);
+
+        assert_eq!(unlocks.time_to_solve(day!(1), 1_733_029_800), Some(600));
+        assert_eq!(unlocks.time_to_solve(day!(1), 1_733_029_000), None);
+        assert_eq!(unlocks.time_to_solve(day!(2), 1_733_029_800), None);
+    }
+
+    #[test]
+    fn formats_seconds() {
+        assert_eq!(format_seconds(59), "00:00:59");
+        assert_eq!(format_seconds(3 * 3600 + 25 * 60 + 7), "03:25:07");
+        assert_eq!(format_seconds(2 * 86_400 + 61), "2d 00:01:01");
+    }
+}